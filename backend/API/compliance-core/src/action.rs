@@ -0,0 +1,133 @@
+use alloc::vec::Vec;
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "openapi")]
+use utoipa::ToSchema;
+
+use crate::amount::Amount;
+use crate::trace::Rule;
+
+/// What the user is doing on the `RWAPool`.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum Action {
+    /// Swap mUSD for the pool's RWA token.
+    #[default]
+    Buy,
+    /// Swap the RWA token back to mUSD.
+    Sell,
+    /// `RWAPool.addLiquidity`.
+    AddLiquidity,
+    /// `RWAPool.removeLiquidity`.
+    RemoveLiquidity,
+}
+
+impl Action {
+    pub const ALL: [Action; 4] = [
+        Action::Buy,
+        Action::Sell,
+        Action::AddLiquidity,
+        Action::RemoveLiquidity,
+    ];
+
+    /// Buys and deposits add to the user's exposure; sells and withdrawals reduce it.
+    pub fn increases_exposure(self) -> bool {
+        matches!(self, Action::Buy | Action::AddLiquidity)
+    }
+}
+
+/// The rules a policy applies to one [`Action`], with optional caps that replace the
+/// policy-wide ones for that action.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+#[serde(deny_unknown_fields)]
+pub struct ActionRules {
+    /// Rules checked for this action; rules not listed are skipped and left out of the trace.
+    #[cfg_attr(feature = "openapi", schema(example = json!(["aml", "residency_ban_list"])))]
+    pub rules: Vec<Rule>,
+    #[serde(default)]
+    #[cfg_attr(feature = "openapi", schema(example = json!(null)))]
+    pub max_single_trade: Option<Amount>,
+    #[serde(default)]
+    #[cfg_attr(feature = "openapi", schema(example = json!(null)))]
+    pub max_total_exposure: Option<Amount>,
+}
+
+impl ActionRules {
+    /// Every rule, under the policy-wide caps.
+    pub fn all() -> Self {
+        Self {
+            rules: Rule::ALL.to_vec(),
+            max_single_trade: None,
+            max_total_exposure: None,
+        }
+    }
+
+    /// Exits stay open after a residency or KYC change; only AML, sanctioned residencies and
+    /// a running lock-up block them. Denominations still apply, except to a full exit.
+    pub fn exit() -> Self {
+        Self {
+            rules: [
+                Rule::Aml,
+                Rule::ResidencyBanList,
+                Rule::LockUp,
+                Rule::MinTrade,
+                Rule::LotSize,
+            ]
+            .to_vec(),
+            max_single_trade: None,
+            max_total_exposure: None,
+        }
+    }
+
+    pub fn applies(&self, rule: Rule) -> bool {
+        self.rules.contains(&rule)
+    }
+}
+
+/// Rule sets for every [`Action`] a policy covers.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+#[serde(deny_unknown_fields)]
+pub struct ActionRuleSets {
+    #[serde(default = "ActionRules::all")]
+    pub buy: ActionRules,
+    #[serde(default = "ActionRules::exit")]
+    pub sell: ActionRules,
+    #[serde(default = "ActionRules::all")]
+    pub add_liquidity: ActionRules,
+    #[serde(default = "ActionRules::exit")]
+    pub remove_liquidity: ActionRules,
+}
+
+impl Default for ActionRuleSets {
+    fn default() -> Self {
+        Self {
+            buy: ActionRules::all(),
+            sell: ActionRules::exit(),
+            add_liquidity: ActionRules::all(),
+            remove_liquidity: ActionRules::exit(),
+        }
+    }
+}
+
+impl ActionRuleSets {
+    pub fn get(&self, action: Action) -> &ActionRules {
+        match action {
+            Action::Buy => &self.buy,
+            Action::Sell => &self.sell,
+            Action::AddLiquidity => &self.add_liquidity,
+            Action::RemoveLiquidity => &self.remove_liquidity,
+        }
+    }
+
+    pub fn get_mut(&mut self, action: Action) -> &mut ActionRules {
+        match action {
+            Action::Buy => &mut self.buy,
+            Action::Sell => &mut self.sell,
+            Action::AddLiquidity => &mut self.add_liquidity,
+            Action::RemoveLiquidity => &mut self.remove_liquidity,
+        }
+    }
+}
@@ -0,0 +1,219 @@
+use core::fmt;
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "openapi")]
+use utoipa::ToSchema;
+
+/// An mUSD amount in base units, the unit `RWAPool.swap` takes as `amountIn`.
+///
+/// Rendered as a string in JSON and TOML, since `uint256` base units do not fit their numbers:
+/// either plain base units (`"10000000000"`) or mUSD with a unit suffix
+/// (`"10_000 mUSD"`, `"12.5 mUSD"`). JSON numbers are rejected: they used to mean whole mUSD,
+/// and reading them as base units would silently shrink them a millionfold. Always written
+/// back as base units, and as a raw `u128` in the zkVM wire format.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+#[cfg_attr(
+    feature = "openapi",
+    schema(value_type = String, example = "10000000000")
+)]
+pub struct Amount(pub u128);
+
+impl Amount {
+    /// Decimals of the mUSD token.
+    pub const DECIMALS: u32 = 6;
+    pub const ZERO: Self = Self(0);
+    /// Stands in for "no limit" when folding caps.
+    pub const MAX: Self = Self(u128::MAX);
+
+    const ONE_MUSD: u128 = 10u128.pow(Self::DECIMALS);
+
+    /// `musd` whole mUSD.
+    pub const fn from_musd(musd: u64) -> Self {
+        Self(musd as u128 * Self::ONE_MUSD)
+    }
+
+    pub const fn base_units(self) -> u128 {
+        self.0
+    }
+
+    pub const fn is_zero(self) -> bool {
+        self.0 == 0
+    }
+
+    pub const fn saturating_add(self, other: Self) -> Self {
+        Self(self.0.saturating_add(other.0))
+    }
+
+    pub const fn saturating_sub(self, other: Self) -> Self {
+        Self(self.0.saturating_sub(other.0))
+    }
+
+    /// `bps` basis points of this amount, rounded down. Never exceeds the amount for
+    /// `bps <= 10_000`; above that, saturates at [`Amount::MAX`].
+    pub const fn mul_bps(self, bps: u16) -> Self {
+        const FULL: u128 = 10_000;
+        let bps = bps as u128;
+        Self(
+            (self.0 / FULL)
+                .saturating_mul(bps)
+                .saturating_add((self.0 % FULL) * bps / FULL),
+        )
+    }
+
+    /// Whether this amount is a whole number of `lot`s. Every amount is a multiple of zero.
+    pub const fn is_multiple_of(self, lot: Self) -> bool {
+        self.round_down_to(lot).0 == self.0
+    }
+
+    /// The largest multiple of `lot` not above this amount.
+    pub const fn round_down_to(self, lot: Self) -> Self {
+        if lot.0 == 0 {
+            return self;
+        }
+        Self(self.0 - self.0 % lot.0)
+    }
+
+    /// The smallest multiple of `lot` not below this amount, saturating at the largest
+    /// representable multiple.
+    pub const fn round_up_to(self, lot: Self) -> Self {
+        if self.is_multiple_of(lot) {
+            return self;
+        }
+        match self.round_down_to(lot).0.checked_add(lot.0) {
+            Some(value) => Self(value),
+            None => self.round_down_to(lot),
+        }
+    }
+}
+
+/// Renders mUSD with as many decimals as needed, e.g. `10000` or `12.5`.
+impl fmt::Display for Amount {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let whole = self.0 / Self::ONE_MUSD;
+        let fraction = self.0 % Self::ONE_MUSD;
+        if fraction == 0 {
+            return write!(f, "{}", whole);
+        }
+        let mut digits = [0u8; Self::DECIMALS as usize];
+        let mut rest = fraction;
+        for digit in digits.iter_mut().rev() {
+            *digit = b'0' + (rest % 10) as u8;
+            rest /= 10;
+        }
+        let len = digits.len() - digits.iter().rev().take_while(|&&d| d == b'0').count();
+        let digits = core::str::from_utf8(&digits[..len]).map_err(|_| fmt::Error)?;
+        write!(f, "{}.{}", whole, digits)
+    }
+}
+
+impl core::str::FromStr for Amount {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let (number, decimals) = match s.strip_suffix("mUSD") {
+            Some(musd) => (musd.trim_end(), Self::DECIMALS),
+            None => (s, 0),
+        };
+        let (whole, fraction) = number.split_once('.').unwrap_or((number, ""));
+        if fraction.len() > decimals as usize {
+            return Err(if decimals == 0 {
+                "amount in base units must be a whole number; append \" mUSD\" for decimals"
+            } else {
+                "amount has more decimals than mUSD"
+            });
+        }
+
+        const OVERFLOW: &str = "amount is too large";
+        if !whole
+            .bytes()
+            .chain(fraction.bytes())
+            .any(|byte| byte.is_ascii_digit())
+        {
+            return Err("amount is empty");
+        }
+        let mut value: u128 = 0;
+        let padding = decimals as usize - fraction.len();
+        let digits = whole
+            .bytes()
+            .chain(fraction.bytes())
+            .chain(core::iter::repeat_n(b'0', padding));
+        for byte in digits {
+            match byte {
+                b'_' => continue,
+                b'0'..=b'9' => {
+                    value = value
+                        .checked_mul(10)
+                        .and_then(|value| value.checked_add(u128::from(byte - b'0')))
+                        .ok_or(OVERFLOW)?;
+                }
+                _ => return Err("amount must be base units or a number followed by \" mUSD\""),
+            }
+        }
+        Ok(Self(value))
+    }
+}
+
+impl Serialize for Amount {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.collect_str(&self.0)
+        } else {
+            self.0.serialize(serializer)
+        }
+    }
+}
+
+/// Reads the string forms, and turns numbers away with a hint at the string to send instead.
+struct AmountVisitor;
+
+impl AmountVisitor {
+    fn number<E: serde::de::Error>(number: impl fmt::Display) -> E {
+        E::custom(format_args!(
+            "amounts are strings of base units or mUSD; for {} whole mUSD send \"{} mUSD\"",
+            number, number
+        ))
+    }
+}
+
+impl serde::de::Visitor<'_> for AmountVisitor {
+    type Value = Amount;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a string of base units or a number followed by \" mUSD\"")
+    }
+
+    fn visit_str<E: serde::de::Error>(self, amount: &str) -> Result<Amount, E> {
+        amount.parse().map_err(E::custom)
+    }
+
+    fn visit_u64<E: serde::de::Error>(self, number: u64) -> Result<Amount, E> {
+        Err(Self::number(number))
+    }
+
+    fn visit_i64<E: serde::de::Error>(self, number: i64) -> Result<Amount, E> {
+        Err(Self::number(number))
+    }
+
+    fn visit_u128<E: serde::de::Error>(self, number: u128) -> Result<Amount, E> {
+        Err(Self::number(number))
+    }
+
+    fn visit_f64<E: serde::de::Error>(self, number: f64) -> Result<Amount, E> {
+        Err(Self::number(number))
+    }
+}
+
+impl<'de> Deserialize<'de> for Amount {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_any(AmountVisitor)
+        } else {
+            u128::deserialize(deserializer).map(Self)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
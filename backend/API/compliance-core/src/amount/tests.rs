@@ -0,0 +1,80 @@
+use alloc::string::ToString;
+
+use super::*;
+
+fn parse(amount: &str) -> Result<Amount, &'static str> {
+    amount.parse()
+}
+
+#[test]
+fn parses_base_units_and_musd() {
+    assert_eq!(parse("10000000000"), Ok(Amount::from_musd(10_000)));
+    assert_eq!(parse("10_000 mUSD"), Ok(Amount::from_musd(10_000)));
+    assert_eq!(parse("12.5 mUSD"), Ok(Amount(12_500_000)));
+    assert_eq!(parse("0.000001 mUSD"), Ok(Amount(1)));
+    assert_eq!(parse(" 1_000_000 "), Ok(Amount(1_000_000)));
+    assert_eq!(parse("12.5mUSD"), Ok(Amount(12_500_000)));
+}
+
+#[test]
+fn rejects_malformed_amounts() {
+    assert_eq!(
+        parse("0.0000001 mUSD"),
+        Err("amount has more decimals than mUSD")
+    );
+    assert_eq!(
+        parse("12.5"),
+        Err("amount in base units must be a whole number; append \" mUSD\" for decimals")
+    );
+    assert_eq!(parse("_ mUSD"), Err("amount is empty"));
+    assert_eq!(parse(""), Err("amount is empty"));
+    assert_eq!(
+        parse("-5 mUSD"),
+        Err("amount must be base units or a number followed by \" mUSD\"")
+    );
+    assert_eq!(
+        parse("10 USD"),
+        Err("amount must be base units or a number followed by \" mUSD\"")
+    );
+}
+
+#[test]
+fn rejects_amounts_beyond_u128() {
+    assert_eq!(parse(&u128::MAX.to_string()), Ok(Amount::MAX));
+    assert_eq!(
+        parse("340282366920938463463374607431768211456"),
+        Err("amount is too large")
+    );
+    // Fits as mUSD digits, but not once scaled to base units.
+    assert_eq!(
+        parse("340282366920938463463374607431768211455 mUSD"),
+        Err("amount is too large")
+    );
+}
+
+#[test]
+fn json_takes_strings_and_turns_numbers_away() {
+    let amount: Amount = serde_json::from_str("\"12.5 mUSD\"").unwrap();
+    assert_eq!(amount, Amount(12_500_000));
+    assert_eq!(serde_json::to_string(&amount).unwrap(), "\"12500000\"");
+
+    let error = serde_json::from_str::<Amount>("10000").unwrap_err();
+    assert!(
+        error
+            .to_string()
+            .contains("for 10000 whole mUSD send \"10000 mUSD\""),
+        "{error}"
+    );
+    assert!(serde_json::from_str::<Amount>("12.5").is_err());
+}
+
+#[test]
+fn mul_bps_rounds_down_and_saturates() {
+    let amount = Amount::from_musd(10_000);
+    assert_eq!(amount.mul_bps(7_500), Amount::from_musd(7_500));
+    assert_eq!(amount.mul_bps(10_000), amount);
+    assert_eq!(Amount(9_999).mul_bps(1), Amount::ZERO);
+    assert_eq!(amount.mul_bps(u16::MAX), Amount(65_535_000_000));
+    assert_eq!(Amount::MAX.mul_bps(10_000), Amount::MAX);
+    assert_eq!(Amount::MAX.mul_bps(10_001), Amount::MAX);
+}
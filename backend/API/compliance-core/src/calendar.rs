@@ -0,0 +1,245 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "openapi")]
+use utoipa::ToSchema;
+
+use crate::volume::SECONDS_PER_DAY;
+
+/// A UTC calendar day, counted in days since 1970-01-01.
+///
+/// Written as `"YYYY-MM-DD"` in JSON and TOML, and as the raw day number in the zkVM wire
+/// format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+#[cfg_attr(feature = "openapi", schema(value_type = String, example = "2026-12-25"))]
+pub struct Date(pub u32);
+
+impl Date {
+    /// The day `timestamp` (Unix seconds) falls on.
+    pub const fn from_timestamp(timestamp: u64) -> Self {
+        Self((timestamp / SECONDS_PER_DAY) as u32)
+    }
+
+    /// Unix seconds of midnight at the start of the day.
+    pub const fn start(self) -> u64 {
+        self.0 as u64 * SECONDS_PER_DAY
+    }
+
+    pub const fn next(self) -> Self {
+        Self(self.0.saturating_add(1))
+    }
+
+    pub const fn weekday(self) -> Weekday {
+        // 1970-01-01 was a Thursday.
+        Weekday::ALL[(self.0 as usize + 3) % 7]
+    }
+
+    /// Year, month and day, after Howard Hinnant's `civil_from_days`.
+    fn civil(self) -> (u64, u64, u64) {
+        let z = u64::from(self.0) + 719_468;
+        let era = z / 146_097;
+        let doe = z - era * 146_097;
+        let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+        let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+        let mp = (5 * doy + 2) / 153;
+        let day = doy - (153 * mp + 2) / 5 + 1;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 };
+        let year = yoe + era * 400 + u64::from(month <= 2);
+        (year, month, day)
+    }
+
+    /// The inverse of [`Date::civil`], after Howard Hinnant's `days_from_civil`.
+    fn from_civil(year: u32, month: u32, day: u32) -> Option<Self> {
+        let leap =
+            year.is_multiple_of(4) && (!year.is_multiple_of(100) || year.is_multiple_of(400));
+        let month_len = match month {
+            2 if leap => 29,
+            2 => 28,
+            4 | 6 | 9 | 11 => 30,
+            1..=12 => 31,
+            _ => return None,
+        };
+        if year < 1970 || day == 0 || day > month_len {
+            return None;
+        }
+        let year = year - u32::from(month <= 2);
+        let era = year / 400;
+        let yoe = year - era * 400;
+        let doy = (153 * if month > 2 { month - 3 } else { month + 9 } + 2) / 5 + day - 1;
+        let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+        (era * 146_097 + doe).checked_sub(719_468).map(Self)
+    }
+}
+
+impl fmt::Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (year, month, day) = self.civil();
+        write!(f, "{:04}-{:02}-{:02}", year, month, day)
+    }
+}
+
+impl core::str::FromStr for Date {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        const FORMAT: &str = "date must be YYYY-MM-DD";
+        let mut parts = s.trim().split('-');
+        let mut next = |len: usize| {
+            parts
+                .next()
+                .filter(|part| part.len() == len && part.bytes().all(|b| b.is_ascii_digit()))
+                .and_then(|part| part.parse::<u32>().ok())
+                .ok_or(FORMAT)
+        };
+        let (year, month, day) = (next(4)?, next(2)?, next(2)?);
+        if parts.next().is_some() {
+            return Err(FORMAT);
+        }
+        Self::from_civil(year, month, day).ok_or("date does not exist or is before 1970")
+    }
+}
+
+impl Serialize for Date {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.collect_str(self)
+        } else {
+            self.0.serialize(serializer)
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Date {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            let date = String::deserialize(deserializer)?;
+            date.parse().map_err(serde::de::Error::custom)
+        } else {
+            u32::deserialize(deserializer).map(Self)
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum Weekday {
+    Monday,
+    Tuesday,
+    Wednesday,
+    Thursday,
+    Friday,
+    Saturday,
+    Sunday,
+}
+
+impl Weekday {
+    pub const ALL: [Weekday; 7] = [
+        Weekday::Monday,
+        Weekday::Tuesday,
+        Weekday::Wednesday,
+        Weekday::Thursday,
+        Weekday::Friday,
+        Weekday::Saturday,
+        Weekday::Sunday,
+    ];
+}
+
+/// A UTC time of day, in seconds since midnight.
+///
+/// Written as `"HH:MM"` in JSON and TOML; `"24:00"` is the end of the day.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+#[cfg_attr(feature = "openapi", schema(value_type = String, example = "14:00"))]
+pub struct TimeOfDay(pub u32);
+
+impl TimeOfDay {
+    pub const END_OF_DAY: Self = Self(SECONDS_PER_DAY as u32);
+
+    pub const fn of(timestamp: u64) -> Self {
+        Self((timestamp % SECONDS_PER_DAY) as u32)
+    }
+}
+
+impl fmt::Display for TimeOfDay {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:02}:{:02}", self.0 / 3_600, self.0 % 3_600 / 60)
+    }
+}
+
+impl core::str::FromStr for TimeOfDay {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        const FORMAT: &str = "time must be HH:MM, from 00:00 to 24:00";
+        let (hours, minutes) = s.trim().split_once(':').ok_or(FORMAT)?;
+        let parse = |part: &str| {
+            (part.len() == 2 && part.bytes().all(|b| b.is_ascii_digit()))
+                .then(|| part.parse::<u32>().ok())
+                .flatten()
+                .ok_or(FORMAT)
+        };
+        let (hours, minutes) = (parse(hours)?, parse(minutes)?);
+        let time = Self(hours * 3_600 + minutes * 60);
+        if minutes >= 60 || time > Self::END_OF_DAY {
+            return Err(FORMAT);
+        }
+        Ok(time)
+    }
+}
+
+impl Serialize for TimeOfDay {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.collect_str(self)
+        } else {
+            self.0.serialize(serializer)
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for TimeOfDay {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            let time = String::deserialize(deserializer)?;
+            time.parse().map_err(serde::de::Error::custom)
+        } else {
+            u32::deserialize(deserializer).map(Self)
+        }
+    }
+}
+
+/// Hours on given weekdays during which the pool accepts trades, in UTC.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+#[serde(deny_unknown_fields)]
+pub struct TradingWindow {
+    /// Days the window is open on. Empty means every day.
+    #[serde(default)]
+    #[cfg_attr(
+        feature = "openapi",
+        schema(example = json!(["monday", "tuesday", "wednesday", "thursday", "friday"]))
+    )]
+    pub weekdays: Vec<Weekday>,
+    #[cfg_attr(feature = "openapi", schema(example = "09:00"))]
+    pub opens: TimeOfDay,
+    /// Exclusive: a request at exactly this time is outside the window.
+    #[cfg_attr(feature = "openapi", schema(example = "14:00"))]
+    pub closes: TimeOfDay,
+}
+
+impl TradingWindow {
+    pub fn is_open_on(&self, weekday: Weekday) -> bool {
+        self.weekdays.is_empty() || self.weekdays.contains(&weekday)
+    }
+
+    pub fn contains(&self, timestamp: u64) -> bool {
+        let time = TimeOfDay::of(timestamp);
+        self.is_open_on(Date::from_timestamp(timestamp).weekday())
+            && self.opens <= time
+            && time < self.closes
+    }
+}
@@ -0,0 +1,763 @@
+use alloc::borrow::ToOwned;
+use alloc::boxed::Box;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "openapi")]
+use utoipa::ToSchema;
+
+use crate::amount::Amount;
+use crate::investor::{InvestorCategory, InvestorType};
+use crate::jurisdiction;
+use crate::types::ComplianceRequest;
+
+/// An eligibility rule over the request's attributes, such as
+/// `accredited OR (kyc_level >= 3 AND residency IN [SG])`.
+///
+/// Written as text in JSON and TOML and parsed, and type-checked, when the policy is loaded;
+/// the zkVM wire format carries the parsed [`Condition`], so the guest only interprets it.
+/// Both forms are held to [`Eligibility::MAX_DEPTH`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+#[cfg_attr(
+    feature = "openapi",
+    schema(value_type = String, example = "accredited OR (kyc_level >= 3 AND residency IN [SG])")
+)]
+pub struct Eligibility(pub Condition);
+
+impl Eligibility {
+    /// Nesting deeper than this is rejected, which bounds the guest's recursion.
+    pub const MAX_DEPTH: usize = 16;
+
+    /// Wraps `condition`, rejecting trees nested deeper than [`Eligibility::MAX_DEPTH`].
+    pub fn new(condition: Condition) -> Result<Self, String> {
+        if condition.depth() > Self::MAX_DEPTH {
+            return Err(too_deep());
+        }
+        Ok(Self(condition))
+    }
+
+    pub fn admits(&self, request: &ComplianceRequest) -> bool {
+        self.0.holds_for(request)
+    }
+}
+
+/// A typed node of an [`Eligibility`] expression.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Condition {
+    All(Vec<Condition>),
+    Any(Vec<Condition>),
+    Not(Box<Condition>),
+    Const(bool),
+    AmlPassed,
+    /// The investor holds the category; `accredited_investor` counts as `accredited`.
+    Holds(InvestorCategory),
+    Level {
+        attribute: LevelAttribute,
+        comparison: Comparison,
+        value: u8,
+    },
+    Amount {
+        attribute: AmountAttribute,
+        comparison: Comparison,
+        value: Amount,
+    },
+    /// Country codes, with jurisdiction groups already expanded.
+    ResidencyIn(Vec<String>),
+    InvestorTypeIn(Vec<InvestorType>),
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum LevelAttribute {
+    KycLevel,
+    RiskScore,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum AmountAttribute {
+    ExposureMusd,
+    RequestedAmount,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Comparison {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+impl LevelAttribute {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            LevelAttribute::KycLevel => "kyc_level",
+            LevelAttribute::RiskScore => "risk_score",
+        }
+    }
+}
+
+impl AmountAttribute {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            AmountAttribute::ExposureMusd => "exposure_musd",
+            AmountAttribute::RequestedAmount => "requested_amount",
+        }
+    }
+}
+
+impl Comparison {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Comparison::Eq => "=",
+            Comparison::Ne => "!=",
+            Comparison::Lt => "<",
+            Comparison::Le => "<=",
+            Comparison::Gt => ">",
+            Comparison::Ge => ">=",
+        }
+    }
+
+    /// Whether `left` compares to `right` this way.
+    pub fn compare<T: Ord>(self, left: T, right: T) -> bool {
+        match self {
+            Comparison::Eq => left == right,
+            Comparison::Ne => left != right,
+            Comparison::Lt => left < right,
+            Comparison::Le => left <= right,
+            Comparison::Gt => left > right,
+            Comparison::Ge => left >= right,
+        }
+    }
+}
+
+impl Condition {
+    pub fn holds_for(&self, request: &ComplianceRequest) -> bool {
+        match self {
+            Condition::All(conditions) => conditions.iter().all(|c| c.holds_for(request)),
+            Condition::Any(conditions) => conditions.iter().any(|c| c.holds_for(request)),
+            Condition::Not(condition) => !condition.holds_for(request),
+            Condition::Const(value) => *value,
+            Condition::AmlPassed => request.aml_passed,
+            Condition::Holds(category) => request.holds(*category),
+            Condition::Level {
+                attribute,
+                comparison,
+                value,
+            } => {
+                let actual = match attribute {
+                    LevelAttribute::KycLevel => request.kyc_level,
+                    LevelAttribute::RiskScore => request.risk_score,
+                };
+                comparison.compare(actual, *value)
+            }
+            Condition::Amount {
+                attribute,
+                comparison,
+                value,
+            } => {
+                let actual = match attribute {
+                    AmountAttribute::ExposureMusd => request.exposure_musd,
+                    AmountAttribute::RequestedAmount => request.requested_amount,
+                };
+                comparison.compare(actual, *value)
+            }
+            // Compared like the residency rules: ignoring case, but not surrounding spaces.
+            Condition::ResidencyIn(codes) => codes
+                .iter()
+                .any(|code| code.eq_ignore_ascii_case(&request.residency)),
+            Condition::InvestorTypeIn(types) => request
+                .investor_type
+                .is_some_and(|kind| types.contains(&kind)),
+        }
+    }
+
+    /// Every node in the tree, this one included, parents before children.
+    pub fn walk(&self, visit: &mut impl FnMut(&Condition)) {
+        visit(self);
+        match self {
+            Condition::All(conditions) | Condition::Any(conditions) => {
+                conditions.iter().for_each(|c| c.walk(visit))
+            }
+            Condition::Not(condition) => condition.walk(visit),
+            _ => {}
+        }
+    }
+
+    /// Levels of `AND`, `OR` and `NOT` above the deepest leaf. Never more than the nesting
+    /// the parser counts for the same expression.
+    pub fn depth(&self) -> usize {
+        match self {
+            Condition::All(conditions) | Condition::Any(conditions) => {
+                1 + conditions.iter().map(Condition::depth).max().unwrap_or(0)
+            }
+            Condition::Not(condition) => 1 + condition.depth(),
+            _ => 0,
+        }
+    }
+
+    fn is_compound(&self) -> bool {
+        matches!(self, Condition::All(_) | Condition::Any(_))
+    }
+}
+
+/// Renders the canonical text, which parses back to the same condition.
+impl fmt::Display for Condition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let operand = |f: &mut fmt::Formatter<'_>, condition: &Condition| {
+            if condition.is_compound() {
+                write!(f, "({})", condition)
+            } else {
+                write!(f, "{}", condition)
+            }
+        };
+        match self {
+            Condition::All(conditions) | Condition::Any(conditions) => {
+                let joiner = if matches!(self, Condition::All(_)) {
+                    " AND "
+                } else {
+                    " OR "
+                };
+                for (index, condition) in conditions.iter().enumerate() {
+                    if index > 0 {
+                        f.write_str(joiner)?;
+                    }
+                    operand(f, condition)?;
+                }
+                Ok(())
+            }
+            Condition::Not(condition) => {
+                f.write_str("NOT ")?;
+                operand(f, condition)
+            }
+            Condition::Const(value) => write!(f, "{}", value),
+            Condition::AmlPassed => f.write_str("aml_passed"),
+            Condition::Holds(category) => {
+                write!(f, "investor_categories CONTAINS {}", category.as_str())
+            }
+            Condition::Level {
+                attribute,
+                comparison,
+                value,
+            } => write!(
+                f,
+                "{} {} {}",
+                attribute.as_str(),
+                comparison.as_str(),
+                value
+            ),
+            Condition::Amount {
+                attribute,
+                comparison,
+                value,
+            } => write!(
+                f,
+                "{} {} {} mUSD",
+                attribute.as_str(),
+                comparison.as_str(),
+                value
+            ),
+            Condition::ResidencyIn(codes) => write!(f, "residency IN [{}]", codes.join(", ")),
+            Condition::InvestorTypeIn(types) => {
+                f.write_str("investor_type IN [")?;
+                for (index, kind) in types.iter().enumerate() {
+                    if index > 0 {
+                        f.write_str(", ")?;
+                    }
+                    f.write_str(kind.as_str())?;
+                }
+                f.write_str("]")
+            }
+        }
+    }
+}
+
+impl fmt::Display for Eligibility {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl core::str::FromStr for Eligibility {
+    type Err = String;
+
+    /// Parses and type-checks an expression. Errors name the offending token and its byte
+    /// offset.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parser = Parser {
+            tokens: tokenize(s)?,
+            position: 0,
+            depth: 0,
+            end: s.len(),
+        };
+        let condition = parser.expression()?;
+        match parser.peek() {
+            None => Ok(Self(condition)),
+            Some(token) => Err(parser.unexpected(token, "AND, OR or the end")),
+        }
+    }
+}
+
+impl Serialize for Eligibility {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.collect_str(self)
+        } else {
+            self.0.serialize(serializer)
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Eligibility {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            let expression = String::deserialize(deserializer)?;
+            expression.parse().map_err(serde::de::Error::custom)
+        } else {
+            let condition = Condition::deserialize(deserializer)?;
+            Self::new(condition).map_err(serde::de::Error::custom)
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Token<'a> {
+    Word(&'a str),
+    Number(&'a str),
+    Text(&'a str),
+    Operator(Comparison),
+    Open,
+    Close,
+    OpenList,
+    CloseList,
+    Comma,
+}
+
+impl fmt::Display for Token<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Token::Word(word) | Token::Number(word) => f.write_str(word),
+            Token::Text(text) => write!(f, "\"{}\"", text),
+            Token::Operator(comparison) => f.write_str(comparison.as_str()),
+            Token::Open => f.write_str("("),
+            Token::Close => f.write_str(")"),
+            Token::OpenList => f.write_str("["),
+            Token::CloseList => f.write_str("]"),
+            Token::Comma => f.write_str(","),
+        }
+    }
+}
+
+fn tokenize(s: &str) -> Result<Vec<(usize, Token<'_>)>, String> {
+    let mut tokens = Vec::new();
+    let mut chars = s.char_indices().peekable();
+    while let Some((start, c)) = chars.next() {
+        let mut take_while = |predicate: fn(char) -> bool| {
+            let mut end = start + c.len_utf8();
+            while let Some(&(index, next)) = chars.peek() {
+                if !predicate(next) {
+                    break;
+                }
+                end = index + next.len_utf8();
+                chars.next();
+            }
+            &s[start..end]
+        };
+        let token = match c {
+            c if c.is_whitespace() => continue,
+            '(' => Token::Open,
+            ')' => Token::Close,
+            '[' => Token::OpenList,
+            ']' => Token::CloseList,
+            ',' => Token::Comma,
+            '≥' => Token::Operator(Comparison::Ge),
+            '≤' => Token::Operator(Comparison::Le),
+            '≠' => Token::Operator(Comparison::Ne),
+            '=' | '!' | '<' | '>' => {
+                let operator = take_while(|next| matches!(next, '=' | '>'));
+                Token::Operator(match operator {
+                    "=" | "==" => Comparison::Eq,
+                    "!=" | "<>" => Comparison::Ne,
+                    "<" => Comparison::Lt,
+                    "<=" => Comparison::Le,
+                    ">" => Comparison::Gt,
+                    ">=" => Comparison::Ge,
+                    _ => return Err(format!("unknown operator {} at {}", operator, start)),
+                })
+            }
+            '"' | '\'' => {
+                let rest = &s[start + 1..];
+                let end = rest
+                    .find(c)
+                    .ok_or_else(|| format!("unterminated string at {}", start))?;
+                for _ in rest[..=end].chars() {
+                    chars.next();
+                }
+                Token::Text(&rest[..end])
+            }
+            c if c.is_ascii_digit() => Token::Number(take_while(|next| {
+                next.is_ascii_digit() || next == '_' || next == '.'
+            })),
+            c if c.is_ascii_alphabetic() || c == '_' => Token::Word(take_while(|next| {
+                next.is_ascii_alphanumeric() || next == '_'
+            })),
+            _ => return Err(format!("unexpected character {:?} at {}", c, start)),
+        };
+        tokens.push((start, token));
+    }
+    Ok(tokens)
+}
+
+struct Parser<'a> {
+    tokens: Vec<(usize, Token<'a>)>,
+    position: usize,
+    depth: usize,
+    end: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn peek(&self) -> Option<Token<'a>> {
+        self.tokens.get(self.position).map(|(_, token)| *token)
+    }
+
+    fn offset(&self) -> usize {
+        self.tokens
+            .get(self.position)
+            .map_or(self.end, |(offset, _)| *offset)
+    }
+
+    fn next(&mut self, expected: &str) -> Result<Token<'a>, String> {
+        let token = self
+            .peek()
+            .ok_or_else(|| format!("expected {} at the end", expected))?;
+        self.position += 1;
+        Ok(token)
+    }
+
+    fn unexpected(&self, token: Token<'_>, expected: &str) -> String {
+        format!(
+            "expected {} at {}, found {}",
+            expected,
+            self.offset(),
+            token
+        )
+    }
+
+    fn keyword(&mut self, keyword: &str) -> bool {
+        let matched =
+            matches!(self.peek(), Some(Token::Word(word)) if word.eq_ignore_ascii_case(keyword));
+        if matched {
+            self.position += 1;
+        }
+        matched
+    }
+
+    fn expect(&mut self, expected: Token<'_>) -> Result<(), String> {
+        match self.peek() {
+            Some(token) if token == expected => {
+                self.position += 1;
+                Ok(())
+            }
+            Some(token) => Err(self.unexpected(token, &expected.to_string())),
+            None => Err(format!("expected {} at the end", expected)),
+        }
+    }
+
+    fn expression(&mut self) -> Result<Condition, String> {
+        self.nested(Self::any)
+    }
+
+    /// Runs `parse` one nesting level deeper, failing past [`Eligibility::MAX_DEPTH`].
+    fn nested(
+        &mut self,
+        parse: fn(&mut Self) -> Result<Condition, String>,
+    ) -> Result<Condition, String> {
+        self.depth += 1;
+        if self.depth > Eligibility::MAX_DEPTH {
+            return Err(too_deep());
+        }
+        let condition = parse(self)?;
+        self.depth -= 1;
+        Ok(condition)
+    }
+
+    fn any(&mut self) -> Result<Condition, String> {
+        let mut conditions = Vec::from([self.all()?]);
+        while self.keyword("OR") {
+            conditions.push(self.all()?);
+        }
+        Ok(flatten(conditions, Condition::Any))
+    }
+
+    fn all(&mut self) -> Result<Condition, String> {
+        let mut conditions = Vec::from([self.negation()?]);
+        while self.keyword("AND") {
+            conditions.push(self.negation()?);
+        }
+        Ok(flatten(conditions, Condition::All))
+    }
+
+    fn negation(&mut self) -> Result<Condition, String> {
+        if self.keyword("NOT") {
+            return Ok(Condition::Not(Box::new(self.nested(Self::negation)?)));
+        }
+        self.primary()
+    }
+
+    fn primary(&mut self) -> Result<Condition, String> {
+        let offset = self.offset();
+        match self.next("a condition")? {
+            Token::Open => {
+                let condition = self.expression()?;
+                self.expect(Token::Close)?;
+                Ok(condition)
+            }
+            Token::Word(word) => self.attribute(word, offset),
+            token => Err(format!(
+                "expected a condition at {}, found {}",
+                offset, token
+            )),
+        }
+    }
+
+    fn attribute(&mut self, name: &str, offset: usize) -> Result<Condition, String> {
+        match name.to_ascii_lowercase().as_str() {
+            "true" => Ok(Condition::Const(true)),
+            "false" => Ok(Condition::Const(false)),
+            "aml_passed" => self.flag(Condition::AmlPassed),
+            "accredited" | "accredited_investor" => {
+                self.flag(Condition::Holds(InvestorCategory::Accredited))
+            }
+            "kyc_level" => self.level(LevelAttribute::KycLevel),
+            "risk_score" => self.level(LevelAttribute::RiskScore),
+            "exposure_musd" => self.amount(AmountAttribute::ExposureMusd),
+            "requested_amount" => self.amount(AmountAttribute::RequestedAmount),
+            "residency" => {
+                let codes = self.membership(|entry| {
+                    jurisdiction::expand(&[entry.to_owned()]).map_err(|_| {
+                        format!("{} is not a country code or jurisdiction group", entry)
+                    })
+                })?;
+                Ok(codes
+                    .map(|codes| {
+                        let mut codes: Vec<String> = codes.into_iter().flatten().collect();
+                        codes.sort();
+                        codes.dedup();
+                        Condition::ResidencyIn(codes)
+                    })
+                    .into_condition())
+            }
+            "investor_type" => {
+                let types = self.membership(|entry| {
+                    InvestorType::ALL
+                        .into_iter()
+                        .find(|kind| kind.as_str().eq_ignore_ascii_case(entry))
+                        .ok_or_else(|| format!("{} is not an investor type", entry))
+                })?;
+                Ok(types
+                    .map(|mut types| {
+                        types.sort();
+                        types.dedup();
+                        Condition::InvestorTypeIn(types)
+                    })
+                    .into_condition())
+            }
+            "investor_categories" => {
+                if !self.keyword("CONTAINS") {
+                    let found = self.peek();
+                    return Err(match found {
+                        Some(token) => self.unexpected(token, "CONTAINS"),
+                        None => "expected CONTAINS at the end".to_string(),
+                    });
+                }
+                let offset = self.offset();
+                let entry = self.literal("an investor category")?;
+                InvestorCategory::ALL
+                    .into_iter()
+                    .find(|category| category.as_str().eq_ignore_ascii_case(entry))
+                    .map(Condition::Holds)
+                    .ok_or_else(|| format!("{} at {} is not an investor category", entry, offset))
+            }
+            _ => Err(format!("unknown attribute {} at {}", name, offset)),
+        }
+    }
+
+    /// A boolean attribute, alone or compared with `true` or `false`.
+    fn flag(&mut self, condition: Condition) -> Result<Condition, String> {
+        let Some(Token::Operator(comparison @ (Comparison::Eq | Comparison::Ne))) = self.peek()
+        else {
+            return Ok(condition);
+        };
+        self.position += 1;
+        let offset = self.offset();
+        let expected = match self.next("true or false")? {
+            Token::Word(word) if word.eq_ignore_ascii_case("true") => true,
+            Token::Word(word) if word.eq_ignore_ascii_case("false") => false,
+            token => {
+                return Err(format!(
+                    "expected true or false at {}, found {}",
+                    offset, token
+                ))
+            }
+        };
+        Ok(if expected == (comparison == Comparison::Eq) {
+            condition
+        } else {
+            Condition::Not(Box::new(condition))
+        })
+    }
+
+    fn comparison(&mut self) -> Result<Comparison, String> {
+        match self.next("a comparison")? {
+            Token::Operator(comparison) => Ok(comparison),
+            token => {
+                self.position -= 1;
+                Err(self.unexpected(token, "a comparison"))
+            }
+        }
+    }
+
+    fn level(&mut self, attribute: LevelAttribute) -> Result<Condition, String> {
+        let comparison = self.comparison()?;
+        let offset = self.offset();
+        let value = match self.next("a number")? {
+            Token::Number(number) => number.parse::<u8>().ok(),
+            _ => None,
+        }
+        .ok_or_else(|| {
+            format!(
+                "{} needs a whole number up to 255 at {}",
+                attribute.as_str(),
+                offset
+            )
+        })?;
+        Ok(Condition::Level {
+            attribute,
+            comparison,
+            value,
+        })
+    }
+
+    fn amount(&mut self, attribute: AmountAttribute) -> Result<Condition, String> {
+        let comparison = self.comparison()?;
+        let offset = self.offset();
+        let amount = match self.next("an amount")? {
+            Token::Number(number) if self.keyword("mUSD") => format!("{} mUSD", number),
+            Token::Number(number) | Token::Text(number) => number.to_string(),
+            token => return Err(format!("expected an amount at {}, found {}", offset, token)),
+        };
+        let value = amount
+            .parse::<Amount>()
+            .map_err(|e| format!("invalid amount at {}: {}", offset, e))?;
+        Ok(Condition::Amount {
+            attribute,
+            comparison,
+            value,
+        })
+    }
+
+    /// `= x`, `!= x`, `IN [x, ...]` or `NOT IN [x, ...]`, with each entry resolved by `resolve`.
+    fn membership<T>(
+        &mut self,
+        resolve: impl Fn(&str) -> Result<T, String>,
+    ) -> Result<Negatable<Vec<T>>, String> {
+        let negated = self.keyword("NOT");
+        if negated || self.keyword("IN") {
+            if negated && !self.keyword("IN") {
+                let found = self.peek();
+                return Err(match found {
+                    Some(token) => self.unexpected(token, "IN"),
+                    None => "expected IN at the end".to_string(),
+                });
+            }
+            self.expect(Token::OpenList)?;
+            let mut entries = Vec::new();
+            loop {
+                entries.push(resolve(self.literal("a value")?)?);
+                if self.peek() == Some(Token::CloseList) {
+                    self.position += 1;
+                    break;
+                }
+                self.expect(Token::Comma)?;
+            }
+            return Ok(Negatable {
+                value: entries,
+                negated,
+            });
+        }
+        let offset = self.offset();
+        match self.comparison()? {
+            comparison @ (Comparison::Eq | Comparison::Ne) => Ok(Negatable {
+                value: Vec::from([resolve(self.literal("a value")?)?]),
+                negated: comparison == Comparison::Ne,
+            }),
+            comparison => Err(format!(
+                "{} at {} only applies to numbers and amounts; use =, != or IN",
+                comparison.as_str(),
+                offset
+            )),
+        }
+    }
+
+    fn literal(&mut self, expected: &str) -> Result<&'a str, String> {
+        let offset = self.offset();
+        match self.next(expected)? {
+            Token::Word(word) | Token::Text(word) => Ok(word),
+            token => Err(format!(
+                "expected {} at {}, found {}",
+                expected, offset, token
+            )),
+        }
+    }
+}
+
+/// A parsed membership test and whether it was written negated.
+struct Negatable<T> {
+    value: T,
+    negated: bool,
+}
+
+impl<T> Negatable<T> {
+    fn map<U>(self, f: impl FnOnce(T) -> U) -> Negatable<U> {
+        Negatable {
+            value: f(self.value),
+            negated: self.negated,
+        }
+    }
+}
+
+impl Negatable<Condition> {
+    fn into_condition(self) -> Condition {
+        if self.negated {
+            Condition::Not(Box::new(self.value))
+        } else {
+            self.value
+        }
+    }
+}
+
+fn flatten(mut conditions: Vec<Condition>, join: fn(Vec<Condition>) -> Condition) -> Condition {
+    if conditions.len() == 1 {
+        conditions.remove(0)
+    } else {
+        join(conditions)
+    }
+}
+
+fn too_deep() -> String {
+    format!(
+        "expression nests deeper than {} levels",
+        Eligibility::MAX_DEPTH
+    )
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,256 @@
+use alloc::string::ToString;
+use alloc::vec;
+
+use super::*;
+use crate::action::Action;
+use crate::types::PoolId;
+
+fn parse(expression: &str) -> Condition {
+    match expression.parse::<Eligibility>() {
+        Ok(eligibility) => eligibility.0,
+        Err(error) => panic!("{expression}: {error}"),
+    }
+}
+
+fn error(expression: &str) -> String {
+    expression.parse::<Eligibility>().unwrap_err()
+}
+
+fn level(attribute: LevelAttribute, comparison: Comparison, value: u8) -> Condition {
+    Condition::Level {
+        attribute,
+        comparison,
+        value,
+    }
+}
+
+fn kyc_at_least(value: u8) -> Condition {
+    level(LevelAttribute::KycLevel, Comparison::Ge, value)
+}
+
+fn accredited() -> Condition {
+    Condition::Holds(InvestorCategory::Accredited)
+}
+
+fn request() -> ComplianceRequest {
+    ComplianceRequest {
+        user: "0x2111222233334444555566667777888899990000".to_string(),
+        pool_id: PoolId::new("gold"),
+        action: Action::Buy,
+        residency: "SG".to_string(),
+        kyc_level: 3,
+        aml_passed: true,
+        accredited_investor: false,
+        investor_type: None,
+        investor_categories: Vec::new(),
+        exposure_musd: Amount::ZERO,
+        requested_amount: Amount::from_musd(10_000),
+        risk_score: 3,
+        allow_partial: false,
+        portfolio: Vec::new(),
+    }
+}
+
+#[test]
+fn and_binds_tighter_than_or() {
+    assert_eq!(
+        parse("accredited OR kyc_level >= 3 AND aml_passed"),
+        Condition::Any(vec![
+            accredited(),
+            Condition::All(vec![kyc_at_least(3), Condition::AmlPassed]),
+        ])
+    );
+    assert_eq!(
+        parse("(accredited OR kyc_level >= 3) AND aml_passed"),
+        Condition::All(vec![
+            Condition::Any(vec![accredited(), kyc_at_least(3)]),
+            Condition::AmlPassed,
+        ])
+    );
+}
+
+#[test]
+fn not_binds_tighter_than_and() {
+    assert_eq!(
+        parse("NOT accredited AND aml_passed"),
+        Condition::All(vec![
+            Condition::Not(Box::new(accredited())),
+            Condition::AmlPassed,
+        ])
+    );
+    assert_eq!(
+        parse("not (accredited and aml_passed)"),
+        Condition::Not(Box::new(Condition::All(vec![
+            accredited(),
+            Condition::AmlPassed,
+        ])))
+    );
+}
+
+#[test]
+fn chains_flatten_into_one_node() {
+    assert_eq!(
+        parse("aml_passed AND accredited AND kyc_level >= 3"),
+        Condition::All(vec![Condition::AmlPassed, accredited(), kyc_at_least(3)])
+    );
+}
+
+#[test]
+fn membership_and_flags_desugar() {
+    assert_eq!(
+        parse("residency NOT IN [sg, FATF_BLACKLIST]"),
+        parse("NOT residency IN [FATF_BLACKLIST, SG]")
+    );
+    assert_eq!(
+        parse("residency != SG"),
+        Condition::Not(Box::new(Condition::ResidencyIn(vec!["SG".to_string()])))
+    );
+    assert_eq!(
+        parse("accredited_investor = false"),
+        Condition::Not(Box::new(accredited()))
+    );
+    assert_eq!(
+        parse("investor_type IN [entity, individual, entity]"),
+        Condition::InvestorTypeIn(vec![InvestorType::Individual, InvestorType::Entity])
+    );
+    assert_eq!(
+        parse("requested_amount < 12.5 mUSD"),
+        Condition::Amount {
+            attribute: AmountAttribute::RequestedAmount,
+            comparison: Comparison::Lt,
+            value: Amount(12_500_000),
+        }
+    );
+}
+
+#[test]
+fn display_parses_back_to_the_same_condition() {
+    for expression in [
+        "accredited OR (kyc_level >= 3 AND residency IN [SG])",
+        "NOT (aml_passed OR risk_score > 4) AND investor_categories CONTAINS qualified_purchaser",
+        "exposure_musd <= 50_000 mUSD AND investor_type = entity",
+        "NOT NOT true",
+    ] {
+        let condition = parse(expression);
+        assert_eq!(parse(&condition.to_string()), condition, "{expression}");
+    }
+}
+
+#[test]
+fn errors_name_the_token_and_offset() {
+    assert_eq!(error("kyc OR aml_passed"), "unknown attribute kyc at 0");
+    assert_eq!(
+        error("aml_passed AND risk_level < 3"),
+        "unknown attribute risk_level at 15"
+    );
+    assert_eq!(
+        error("kyc_level >= three"),
+        "kyc_level needs a whole number up to 255 at 13"
+    );
+    assert_eq!(
+        error("kyc_level >= 300"),
+        "kyc_level needs a whole number up to 255 at 13"
+    );
+    assert_eq!(
+        error("residency < SG"),
+        "< at 10 only applies to numbers and amounts; use =, != or IN"
+    );
+    assert_eq!(
+        error("residency IN [XX]"),
+        "XX is not a country code or jurisdiction group"
+    );
+    assert_eq!(
+        error("investor_categories CONTAINS gold"),
+        "gold at 29 is not an investor category"
+    );
+    assert_eq!(error("(aml_passed"), "expected ) at the end");
+    assert_eq!(
+        error("aml_passed accredited"),
+        "expected AND, OR or the end at 11, found accredited"
+    );
+    assert_eq!(error("aml_passed AND"), "expected a condition at the end");
+    assert_eq!(error("kyc_level => 3"), "unknown operator => at 10");
+    assert_eq!(
+        error("aml_passed & accredited"),
+        "unexpected character '&' at 11"
+    );
+}
+
+#[test]
+fn nesting_is_bounded() {
+    let nested = |depth: usize| {
+        let mut expression = "aml_passed".to_string();
+        for _ in 1..depth {
+            expression = format!("({expression})");
+        }
+        expression
+    };
+    assert_eq!(parse(&nested(Eligibility::MAX_DEPTH)), Condition::AmlPassed);
+    assert_eq!(
+        error(&nested(Eligibility::MAX_DEPTH + 1)),
+        "expression nests deeper than 16 levels"
+    );
+
+    let negated = |depth: usize| format!("{}aml_passed", "NOT ".repeat(depth - 1));
+    assert!(negated(Eligibility::MAX_DEPTH)
+        .parse::<Eligibility>()
+        .is_ok());
+    assert_eq!(
+        error(&negated(Eligibility::MAX_DEPTH + 1)),
+        "expression nests deeper than 16 levels"
+    );
+}
+
+#[test]
+fn parsed_conditions_fit_the_wire_bound() {
+    let condition = parse(&format!(
+        "{}aml_passed",
+        "NOT ".repeat(Eligibility::MAX_DEPTH - 1)
+    ));
+    assert!(Eligibility::new(condition.clone()).is_ok());
+
+    let too_deep = Condition::Not(Box::new(Condition::Not(Box::new(condition))));
+    assert_eq!(too_deep.depth(), Eligibility::MAX_DEPTH + 1);
+    assert_eq!(
+        Eligibility::new(too_deep).unwrap_err(),
+        "expression nests deeper than 16 levels"
+    );
+}
+
+#[test]
+fn residency_is_compared_like_the_residency_rules() {
+    let singapore = Eligibility(parse("residency IN [SG]"));
+    assert!(singapore.admits(&request()));
+
+    let lowercase = ComplianceRequest {
+        residency: "sg".to_string(),
+        ..request()
+    };
+    assert!(singapore.admits(&lowercase));
+
+    let padded = ComplianceRequest {
+        residency: " SG".to_string(),
+        ..request()
+    };
+    assert!(!singapore.admits(&padded));
+}
+
+#[test]
+fn evaluates_against_the_request() {
+    let rule = Eligibility(parse(
+        "accredited OR (kyc_level >= 3 AND residency IN [SG] AND requested_amount <= 10_000 mUSD)",
+    ));
+    assert!(rule.admits(&request()));
+
+    let larger = ComplianceRequest {
+        requested_amount: Amount::from_musd(10_001),
+        ..request()
+    };
+    assert!(!rule.admits(&larger));
+
+    let accredited = ComplianceRequest {
+        accredited_investor: true,
+        ..larger
+    };
+    assert!(rule.admits(&accredited));
+}
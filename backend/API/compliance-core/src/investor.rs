@@ -0,0 +1,62 @@
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "openapi")]
+use utoipa::ToSchema;
+
+/// Legal form of the investor.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum InvestorType {
+    /// A natural person.
+    Individual,
+    /// A company, fund, trust or other legal person.
+    Entity,
+}
+
+impl InvestorType {
+    pub const ALL: [InvestorType; 2] = [InvestorType::Individual, InvestorType::Entity];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            InvestorType::Individual => "individual",
+            InvestorType::Entity => "entity",
+        }
+    }
+}
+
+/// A regulatory classification the identity provider has verified the investor for.
+///
+/// Policies require categories by these names, so a new offering type only needs a new
+/// variant here.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum InvestorCategory {
+    /// US accredited investor (Regulation D, Rule 501).
+    Accredited,
+    /// US qualified purchaser (Investment Company Act, section 2(a)(51)).
+    QualifiedPurchaser,
+    /// EU professional client (MiFID II, Annex II).
+    ProfessionalClient,
+    /// Regulated financial institution: bank, insurer, investment firm or fund.
+    Institutional,
+}
+
+impl InvestorCategory {
+    pub const ALL: [InvestorCategory; 4] = [
+        InvestorCategory::Accredited,
+        InvestorCategory::QualifiedPurchaser,
+        InvestorCategory::ProfessionalClient,
+        InvestorCategory::Institutional,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            InvestorCategory::Accredited => "accredited",
+            InvestorCategory::QualifiedPurchaser => "qualified_purchaser",
+            InvestorCategory::ProfessionalClient => "professional_client",
+            InvestorCategory::Institutional => "institutional",
+        }
+    }
+}
@@ -0,0 +1,110 @@
+//! ISO 3166-1 alpha-2 country codes and the named jurisdiction groups policies may use in
+//! their residency lists.
+//!
+//! Group membership is a snapshot of the published lists. Policies are expanded to plain codes
+//! when they are loaded, so a membership change here changes the hash of every policy that
+//! uses the group.
+
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+
+/// Every officially assigned ISO 3166-1 alpha-2 code.
+const COUNTRY_CODES: [&str; 249] = [
+    "AD", "AE", "AF", "AG", "AI", "AL", "AM", "AO", "AQ", "AR", "AS", "AT", "AU", "AW", "AX", "AZ",
+    "BA", "BB", "BD", "BE", "BF", "BG", "BH", "BI", "BJ", "BL", "BM", "BN", "BO", "BQ", "BR", "BS",
+    "BT", "BV", "BW", "BY", "BZ", "CA", "CC", "CD", "CF", "CG", "CH", "CI", "CK", "CL", "CM", "CN",
+    "CO", "CR", "CU", "CV", "CW", "CX", "CY", "CZ", "DE", "DJ", "DK", "DM", "DO", "DZ", "EC", "EE",
+    "EG", "EH", "ER", "ES", "ET", "FI", "FJ", "FK", "FM", "FO", "FR", "GA", "GB", "GD", "GE", "GF",
+    "GG", "GH", "GI", "GL", "GM", "GN", "GP", "GQ", "GR", "GS", "GT", "GU", "GW", "GY", "HK", "HM",
+    "HN", "HR", "HT", "HU", "ID", "IE", "IL", "IM", "IN", "IO", "IQ", "IR", "IS", "IT", "JE", "JM",
+    "JO", "JP", "KE", "KG", "KH", "KI", "KM", "KN", "KP", "KR", "KW", "KY", "KZ", "LA", "LB", "LC",
+    "LI", "LK", "LR", "LS", "LT", "LU", "LV", "LY", "MA", "MC", "MD", "ME", "MF", "MG", "MH", "MK",
+    "ML", "MM", "MN", "MO", "MP", "MQ", "MR", "MS", "MT", "MU", "MV", "MW", "MX", "MY", "MZ", "NA",
+    "NC", "NE", "NF", "NG", "NI", "NL", "NO", "NP", "NR", "NU", "NZ", "OM", "PA", "PE", "PF", "PG",
+    "PH", "PK", "PL", "PM", "PN", "PR", "PS", "PT", "PW", "PY", "QA", "RE", "RO", "RS", "RU", "RW",
+    "SA", "SB", "SC", "SD", "SE", "SG", "SH", "SI", "SJ", "SK", "SL", "SM", "SN", "SO", "SR", "SS",
+    "ST", "SV", "SX", "SY", "SZ", "TC", "TD", "TF", "TG", "TH", "TJ", "TK", "TL", "TM", "TN", "TO",
+    "TR", "TT", "TV", "TW", "TZ", "UA", "UG", "UM", "US", "UY", "UZ", "VA", "VC", "VE", "VG", "VI",
+    "VN", "VU", "WF", "WS", "YE", "YT", "ZA", "ZM", "ZW",
+];
+
+const EU: &[&str] = &[
+    "AT", "BE", "BG", "CY", "CZ", "DE", "DK", "EE", "ES", "FI", "FR", "GR", "HR", "HU", "IE", "IT",
+    "LT", "LU", "LV", "MT", "NL", "PL", "PT", "RO", "SE", "SI", "SK",
+];
+
+const EEA: &[&str] = &[
+    "AT", "BE", "BG", "CY", "CZ", "DE", "DK", "EE", "ES", "FI", "FR", "GR", "HR", "HU", "IE", "IS",
+    "IT", "LI", "LT", "LU", "LV", "MT", "NL", "NO", "PL", "PT", "RO", "SE", "SI", "SK",
+];
+
+/// FATF high-risk jurisdictions subject to a call for action.
+const FATF_BLACKLIST: &[&str] = &["IR", "KP", "MM"];
+
+/// Countries under comprehensive OFAC sanctions programs. Region-level programs (Crimea and
+/// the so-called DNR and LNR) have no ISO 3166-1 code and must be screened upstream.
+const OFAC_COMPREHENSIVE: &[&str] = &["CU", "IR", "KP"];
+
+/// Named groups that can stand in for a list of codes in a policy.
+pub const GROUPS: &[(&str, &[&str])] = &[
+    ("EU", EU),
+    ("EEA", EEA),
+    ("FATF_BLACKLIST", FATF_BLACKLIST),
+    ("OFAC_COMPREHENSIVE", OFAC_COMPREHENSIVE),
+];
+
+/// Whether `code` is an assigned ISO 3166-1 alpha-2 code. Expects upper case.
+pub fn is_country_code(code: &str) -> bool {
+    COUNTRY_CODES.contains(&code)
+}
+
+/// Members of the named group, matched case-insensitively.
+pub fn group(name: &str) -> Option<&'static [&'static str]> {
+    GROUPS
+        .iter()
+        .find(|(group, _)| group.eq_ignore_ascii_case(name))
+        .map(|(_, members)| *members)
+}
+
+/// Resolves a residency list to a sorted, de-duplicated list of country codes, expanding
+/// group names along the way.
+pub fn expand(entries: &[String]) -> Result<Vec<String>, JurisdictionError> {
+    let mut codes = Vec::new();
+    for entry in entries {
+        let normalized = entry.trim().to_ascii_uppercase();
+        if let Some(members) = group(&normalized) {
+            codes.extend(members.iter().map(|code| code.to_string()));
+        } else if is_country_code(&normalized) {
+            codes.push(normalized);
+        } else {
+            return Err(JurisdictionError {
+                entry: entry.clone(),
+            });
+        }
+    }
+    codes.sort();
+    codes.dedup();
+    Ok(codes)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JurisdictionError {
+    pub entry: String,
+}
+
+impl fmt::Display for JurisdictionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:?} is neither an ISO 3166-1 alpha-2 code nor a jurisdiction group",
+            self.entry
+        )?;
+        if self.entry.trim().eq_ignore_ascii_case("UK") {
+            f.write_str(" (the United Kingdom is GB)")?;
+        }
+        Ok(())
+    }
+}
+
+impl core::error::Error for JurisdictionError {}
@@ -0,0 +1,38 @@
+//! Compliance types and the pool policy evaluator shared by the host and the guest.
+//!
+//! The host runs [`evaluate`] as a preliminary check before submitting a proof request,
+//! and the guest runs the very same function inside the zkVM to produce the journal.
+//! Keeping both in one `no_std` crate means the two can never drift apart.
+
+// utoipa's derives expand to `std` paths, so only the host-side `openapi` build links std;
+// unit tests link it too, for the test harness.
+#![cfg_attr(not(any(test, feature = "openapi")), no_std)]
+
+extern crate alloc;
+
+mod action;
+mod amount;
+mod calendar;
+pub mod canonical;
+pub mod eligibility;
+mod investor;
+pub mod jurisdiction;
+mod policy;
+mod trace;
+mod types;
+mod violation;
+mod volume;
+
+pub use action::{Action, ActionRuleSets, ActionRules};
+pub use amount::Amount;
+pub use calendar::{Date, TimeOfDay, TradingWindow, Weekday};
+pub use eligibility::{AmountAttribute, Comparison, Condition, Eligibility, LevelAttribute};
+pub use investor::{InvestorCategory, InvestorType};
+pub use policy::{evaluate, evaluate_with_trace, KycTier, PoolPolicy, RiskBand};
+pub use trace::{Rule, RuleCheck, TraceValue};
+pub use types::{
+    AddressOverride, ComplianceInput, ComplianceOutcome, ComplianceRequest, EvaluationContext,
+    PolicyHash, PoolExposure, PoolId,
+};
+pub use violation::Violation;
+pub use volume::{VolumeWindow, WindowAllowance, WindowVolume, SECONDS_PER_DAY};
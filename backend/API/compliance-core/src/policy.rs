@@ -0,0 +1,858 @@
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "openapi")]
+use utoipa::ToSchema;
+
+use crate::action::{Action, ActionRuleSets, ActionRules};
+use crate::amount::Amount;
+use crate::calendar::{Date, TimeOfDay, TradingWindow};
+use crate::canonical;
+use crate::eligibility::Eligibility;
+use crate::investor::{InvestorCategory, InvestorType};
+use crate::jurisdiction;
+use crate::trace::{Rule, RuleCheck, TraceValue};
+use crate::types::{
+    AddressOverride, ComplianceOutcome, ComplianceRequest, EvaluationContext, PolicyHash,
+};
+use crate::violation::Violation;
+use crate::volume::{VolumeWindow, WindowAllowance, SECONDS_PER_DAY};
+
+/// Rules a pool applies to every compliance request.
+///
+/// Policies are loaded by the host from configuration and handed to the guest alongside the
+/// request, so retuning a pool never requires a new guest image. Several pools may share
+/// one policy.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+#[serde(deny_unknown_fields)]
+pub struct PoolPolicy {
+    /// Key pools use to reference this policy from the registry.
+    #[cfg_attr(feature = "openapi", schema(example = "gold"))]
+    pub policy_id: String,
+    #[cfg_attr(feature = "openapi", schema(example = "Gold"))]
+    pub name: String,
+    /// Revision of the policy; each version of a `policy_id` has its own number.
+    #[serde(default = "PoolPolicy::first_version")]
+    #[cfg_attr(feature = "openapi", schema(example = 1))]
+    pub version: u32,
+    /// Unix seconds from which this version applies, until a later version takes over.
+    #[serde(default)]
+    #[cfg_attr(feature = "openapi", schema(example = 0))]
+    pub effective_from: u64,
+    /// Residencies the pool is open to. Empty means every residency not banned.
+    #[serde(default)]
+    #[cfg_attr(feature = "openapi", schema(example = json!(["US", "CA", "DE"])))]
+    pub allowed_residencies: Vec<String>,
+    #[serde(default)]
+    #[cfg_attr(feature = "openapi", schema(example = json!([])))]
+    pub banned_residencies: Vec<String>,
+    #[cfg_attr(feature = "openapi", schema(example = "50000000000"))]
+    pub max_single_trade: Amount,
+    #[cfg_attr(feature = "openapi", schema(example = "150000000000"))]
+    pub max_total_exposure: Amount,
+    #[cfg_attr(feature = "openapi", schema(example = 2))]
+    pub min_kyc_level: u8,
+    #[cfg_attr(feature = "openapi", schema(example = 4))]
+    pub max_risk_score: u8,
+    #[serde(default)]
+    #[cfg_attr(feature = "openapi", schema(example = false))]
+    pub require_accreditation: bool,
+    /// Investor types the pool is open to. Empty means every type, including requests that
+    /// report none.
+    #[serde(default)]
+    #[cfg_attr(feature = "openapi", schema(example = json!(["individual", "entity"])))]
+    pub allowed_investor_types: Vec<InvestorType>,
+    /// Classifications the investor must hold, every one of them.
+    #[serde(default)]
+    #[cfg_attr(feature = "openapi", schema(example = json!(["qualified_purchaser"])))]
+    pub required_investor_categories: Vec<InvestorCategory>,
+    /// Rolling-window caps on cumulative approved volume for buys and liquidity deposits.
+    #[serde(default)]
+    #[cfg_attr(
+        feature = "openapi",
+        schema(example = json!([{"period_days": 30, "max_volume": "100000000000"}]))
+    )]
+    pub volume_windows: Vec<VolumeWindow>,
+    /// Per-tier trade and exposure caps that replace the policy-wide ones for users who reach
+    /// the tier. Users who reach no tier keep the policy-wide caps.
+    #[serde(default)]
+    #[cfg_attr(
+        feature = "openapi",
+        schema(example = json!([
+            {"name": "standard", "min_kyc_level": 2, "max_single_trade": "10000000000", "max_total_exposure": "50000000000"},
+            {"name": "enhanced", "min_kyc_level": 3, "max_single_trade": "50000000000", "max_total_exposure": "150000000000"},
+            {"name": "accredited", "min_kyc_level": 3, "require_accreditation": true, "max_single_trade": "200000000000", "max_total_exposure": "500000000000"}
+        ]))
+    )]
+    pub tiers: Vec<KycTier>,
+    /// Scales the exposure cap by risk score. The first band whose `max_risk_score` covers the
+    /// user's score applies; scores above every band keep the full cap.
+    #[serde(default)]
+    #[cfg_attr(
+        feature = "openapi",
+        schema(example = json!([
+            {"max_risk_score": 1, "allocation_bps": 10000},
+            {"max_risk_score": 3, "allocation_bps": 7500},
+            {"max_risk_score": 4, "allocation_bps": 5000}
+        ]))
+    )]
+    pub risk_bands: Vec<RiskBand>,
+    /// Cap on the user's exposure summed across every RWA pool, checked on top of this pool's
+    /// own cap. A tier's `max_portfolio_exposure` takes precedence.
+    #[serde(default)]
+    #[cfg_attr(feature = "openapi", schema(example = "500000000000"))]
+    pub max_portfolio_exposure: Option<Amount>,
+    /// Days after a purchase during which the position can be neither sold nor withdrawn, as
+    /// Reg D offerings require. Each buy or deposit restarts it for the whole position.
+    /// Checked by the `lock_up` rule.
+    #[serde(default)]
+    #[cfg_attr(feature = "openapi", schema(example = 365))]
+    pub lockup_days: Option<u32>,
+    /// Most distinct holders the pool may have, as private placements require. Once reached,
+    /// only existing holders can buy or deposit. Checked by the `investor_cap` rule.
+    #[serde(default)]
+    #[cfg_attr(feature = "openapi", schema(example = 99))]
+    pub max_investors: Option<u32>,
+    /// Smallest amount a single trade may be, as bond and fund denominations require.
+    /// Checked by the `min_trade` rule.
+    #[serde(default)]
+    #[cfg_attr(feature = "openapi", schema(example = "1000000000"))]
+    pub min_trade: Option<Amount>,
+    /// Trades must be a whole number of lots of this size. Checked by the `lot_size` rule.
+    #[serde(default)]
+    #[cfg_attr(feature = "openapi", schema(example = "1000000000"))]
+    pub lot_size: Option<Amount>,
+    /// Smallest first buy or deposit into the pool, i.e. while the user has no exposure.
+    /// Checked by the `min_initial_investment` rule.
+    #[serde(default)]
+    #[cfg_attr(feature = "openapi", schema(example = "25000000000"))]
+    pub min_initial_investment: Option<Amount>,
+    /// UTC hours the pool accepts trades in, such as a fund's NAV windows. Empty means any
+    /// time. Checked by the `trading_window` rule.
+    #[serde(default)]
+    #[cfg_attr(
+        feature = "openapi",
+        schema(example = json!([{"weekdays": ["monday", "tuesday", "wednesday", "thursday", "friday"], "opens": "09:00", "closes": "14:00"}]))
+    )]
+    pub trading_windows: Vec<TradingWindow>,
+    /// Name of the holiday calendar whose dates the host merges into `holidays` at load.
+    #[serde(default)]
+    #[cfg_attr(feature = "openapi", schema(example = "us_market"))]
+    pub holiday_calendar: Option<String>,
+    /// UTC days the pool is closed. Checked by the `business_day` rule.
+    #[serde(default)]
+    #[cfg_attr(feature = "openapi", schema(example = json!(["2026-12-25"])))]
+    pub holidays: Vec<Date>,
+    /// Custom eligibility expression over the request, for rules the fixed fields cannot
+    /// express. Checked by the `eligibility` rule.
+    #[serde(default)]
+    #[cfg_attr(
+        feature = "openapi",
+        schema(value_type = Option<String>, example = "accredited OR (kyc_level >= 3 AND residency IN [SG])")
+    )]
+    pub eligibility: Option<Eligibility>,
+    /// Which rules apply to each action. Buys and liquidity deposits check every rule by
+    /// default; sells and withdrawals only check AML, the ban list, the lock-up and the trade
+    /// size floors.
+    #[serde(default)]
+    pub actions: ActionRuleSets,
+}
+
+impl PoolPolicy {
+    /// How far [`PoolPolicy::next_trading_time`] looks for an opening.
+    pub const TRADING_LOOKAHEAD_DAYS: u32 = 31;
+
+    const fn first_version() -> u32 {
+        1
+    }
+
+    /// Canonical hash of every rule in this policy.
+    ///
+    /// Residency lists are hashed in the order given, so callers that want equivalent
+    /// documents to hash identically should normalize them first (the host does at load).
+    pub fn policy_hash(&self) -> PolicyHash {
+        PolicyHash(canonical::policy_digest(self))
+    }
+
+    pub fn rules_for(&self, action: Action) -> &ActionRules {
+        self.actions.get(action)
+    }
+
+    /// The highest tier `request` reaches. Tiers are listed from lowest to highest.
+    pub fn tier_for(&self, request: &ComplianceRequest) -> Option<&KycTier> {
+        self.tiers.iter().rev().find(|tier| tier.admits(request))
+    }
+
+    /// Single-trade limit for `request`: the action's own cap if set, then the user's tier
+    /// cap, then the policy-wide one.
+    pub fn single_trade_limit(&self, request: &ComplianceRequest) -> Amount {
+        self.rules_for(request.action)
+            .max_single_trade
+            .or_else(|| self.tier_for(request).map(|tier| tier.max_single_trade))
+            .unwrap_or(self.max_single_trade)
+    }
+
+    /// Exposure cap for `request`, resolved like [`PoolPolicy::single_trade_limit`] and then
+    /// scaled by the user's risk band.
+    pub fn exposure_cap(&self, request: &ComplianceRequest) -> Amount {
+        let cap = self
+            .rules_for(request.action)
+            .max_total_exposure
+            .or_else(|| self.tier_for(request).map(|tier| tier.max_total_exposure))
+            .unwrap_or(self.max_total_exposure);
+        match self.risk_band_for(request) {
+            Some(band) => band.scale(cap),
+            None => cap,
+        }
+    }
+
+    /// Portfolio cap for `request`: the user's tier cap if set, then the policy-wide one.
+    pub fn portfolio_cap(&self, request: &ComplianceRequest) -> Option<Amount> {
+        self.tier_for(request)
+            .and_then(|tier| tier.max_portfolio_exposure)
+            .or(self.max_portfolio_exposure)
+    }
+
+    /// End of the lock-up from the user's last purchase, if it is still running at
+    /// `context.now`.
+    pub fn unlocks_at(&self, context: &EvaluationContext) -> Option<u64> {
+        self.lockup_end(context)
+            .filter(|&unlocks_at| unlocks_at > context.now)
+    }
+
+    fn lockup_end(&self, context: &EvaluationContext) -> Option<u64> {
+        let period = u64::from(self.lockup_days?) * SECONDS_PER_DAY;
+        Some(context.last_acquired_at?.saturating_add(period))
+    }
+
+    /// Slots left for new investors when the pool has `investor_count` holders; `None` when the
+    /// pool has no investor cap.
+    pub fn investor_slots_left(&self, investor_count: u32) -> Option<u32> {
+        self.max_investors
+            .map(|max| max.saturating_sub(investor_count))
+    }
+
+    /// Whether `timestamp` falls inside a trading window, or the pool declares none.
+    pub fn in_trading_window(&self, timestamp: u64) -> bool {
+        self.trading_windows.is_empty()
+            || self
+                .trading_windows
+                .iter()
+                .any(|window| window.contains(timestamp))
+    }
+
+    pub fn is_holiday(&self, date: Date) -> bool {
+        self.holidays.contains(&date)
+    }
+
+    /// Earliest time from `timestamp` on that is inside a trading window and not on a holiday,
+    /// looking up to [`PoolPolicy::TRADING_LOOKAHEAD_DAYS`] ahead.
+    pub fn next_trading_time(&self, timestamp: u64) -> Option<u64> {
+        let mut date = Date::from_timestamp(timestamp);
+        for _ in 0..=Self::TRADING_LOOKAHEAD_DAYS {
+            if !self.is_holiday(date) {
+                let from = TimeOfDay::of(timestamp.max(date.start()));
+                let opens = if self.trading_windows.is_empty() {
+                    Some(from)
+                } else {
+                    self.trading_windows
+                        .iter()
+                        .filter(|window| window.is_open_on(date.weekday()) && from < window.closes)
+                        .map(|window| window.opens.max(from))
+                        .min()
+                };
+                if let Some(opens) = opens {
+                    return Some(date.start() + u64::from(opens.0));
+                }
+            }
+            date = date.next();
+        }
+        None
+    }
+
+    /// The band covering `request.risk_score`. Bands are listed by ascending `max_risk_score`.
+    pub fn risk_band_for(&self, request: &ComplianceRequest) -> Option<&RiskBand> {
+        self.risk_bands
+            .iter()
+            .find(|band| request.risk_score <= band.max_risk_score)
+    }
+
+    /// Largest amount the amount rules that apply to `request.action` would let through.
+    pub fn max_amount(&self, request: &ComplianceRequest, context: &EvaluationContext) -> Amount {
+        let rules = self.rules_for(request.action);
+        let mut max = Amount::MAX;
+        if rules.applies(Rule::SingleTradeLimit) {
+            max = max.min(self.single_trade_limit(request));
+        }
+        if rules.applies(Rule::ExposureCap) && request.action.increases_exposure() {
+            max = max.min(
+                self.exposure_cap(request)
+                    .saturating_sub(request.exposure_musd),
+            );
+        }
+        if rules.applies(Rule::VolumeWindow) && request.action.increases_exposure() {
+            for window in &self.volume_windows {
+                max = max.min(
+                    window
+                        .max_volume
+                        .saturating_sub(context.window_used(window.period_days)),
+                );
+            }
+        }
+        if let Some(cap) = self.portfolio_cap(request) {
+            if rules.applies(Rule::PortfolioCap) && request.action.increases_exposure() {
+                max = max.min(cap.saturating_sub(request.portfolio_exposure()));
+            }
+        }
+        if max == Amount::MAX {
+            return max;
+        }
+        if let Some(lot) = self.lot_size.filter(|_| rules.applies(Rule::LotSize)) {
+            max = max.round_down_to(lot);
+        }
+        // Nothing fits when the room left is below the smallest trade the pool takes.
+        if max < self.min_amount(request) {
+            return Amount::ZERO;
+        }
+        max
+    }
+
+    /// Smallest amount the trade size floors that apply to `request` would let through,
+    /// rounded up to a whole lot; zero when none apply.
+    pub fn min_amount(&self, request: &ComplianceRequest) -> Amount {
+        let rules = self.rules_for(request.action);
+        let mut min = Amount::ZERO;
+        if let Some(minimum) = self.min_trade.filter(|_| rules.applies(Rule::MinTrade)) {
+            min = min.max(minimum);
+        }
+        if let Some(minimum) = self.min_initial_investment.filter(|_| {
+            rules.applies(Rule::MinInitialInvestment) && request.is_initial_investment()
+        }) {
+            min = min.max(minimum);
+        }
+        match self.lot_size.filter(|_| rules.applies(Rule::LotSize)) {
+            Some(lot) => min.round_up_to(lot),
+            None => min,
+        }
+    }
+}
+
+pub fn evaluate(
+    request: &ComplianceRequest,
+    policy: &PoolPolicy,
+    context: &EvaluationContext,
+) -> ComplianceOutcome {
+    evaluate_with_trace(request, policy, context).0
+}
+
+/// Evaluates `request` like [`evaluate`] and also returns every rule that was checked, in
+/// order, with its threshold, the value provided and whether it passed.
+///
+/// Only the rules the policy applies to `request.action` are checked.
+pub fn evaluate_with_trace(
+    request: &ComplianceRequest,
+    policy: &PoolPolicy,
+    context: &EvaluationContext,
+) -> (ComplianceOutcome, Vec<RuleCheck>) {
+    let normalized_residency = request.residency.to_ascii_uppercase();
+    let rules = policy.rules_for(request.action);
+    let tier = policy.tier_for(request);
+    let single_trade_limit = policy.single_trade_limit(request);
+    let exposure_cap = policy.exposure_cap(request);
+
+    let mut violations: Vec<Violation> = Vec::new();
+    let mut trace: Vec<RuleCheck> = Vec::new();
+    let allowlisted = context.address_override == Some(AddressOverride::Allow);
+    if let Some(address_override) = context.address_override {
+        let denied = address_override == AddressOverride::Deny;
+        trace.push(RuleCheck {
+            rule: Rule::AddressOverride,
+            threshold: TraceValue::Flag(false),
+            actual: TraceValue::Flag(denied),
+            passed: !denied,
+        });
+        if denied {
+            violations.push(Violation::AddressDenied);
+        }
+    }
+
+    let mut check = |rule: Rule, threshold: TraceValue, actual: TraceValue, passed: bool| {
+        if !rules.applies(rule) || (allowlisted && rule.is_overridable()) {
+            return true;
+        }
+        trace.push(RuleCheck {
+            rule,
+            threshold,
+            actual,
+            passed,
+        });
+        passed
+    };
+
+    if !check(
+        Rule::Aml,
+        TraceValue::Flag(true),
+        TraceValue::Flag(request.aml_passed),
+        request.aml_passed,
+    ) {
+        violations.push(Violation::AmlFailed);
+    }
+
+    let residency_provided = !normalized_residency.is_empty();
+    let residency_valid = jurisdiction::is_country_code(&normalized_residency);
+    // Sanctions screening needs a residency even for allowlisted addresses.
+    let residency_checked = rules.applies(Rule::ResidencyBanList)
+        || (!allowlisted && rules.applies(Rule::ResidencyAllowList));
+    if residency_checked && !residency_provided {
+        violations.push(Violation::ResidencyMissing);
+    } else if residency_checked && !residency_valid {
+        violations.push(Violation::ResidencyInvalid {
+            residency: normalized_residency.clone(),
+        });
+    }
+
+    let open_to_residency = policy.allowed_residencies.is_empty()
+        || contains(&policy.allowed_residencies, normalized_residency.as_str());
+    if !check(
+        Rule::ResidencyAllowList,
+        TraceValue::Residencies(policy.allowed_residencies.clone()),
+        TraceValue::Residency(normalized_residency.clone()),
+        residency_valid && open_to_residency,
+    ) && residency_valid
+    {
+        violations.push(Violation::ResidencyNotAllowed {
+            residency: normalized_residency.clone(),
+        });
+    }
+
+    let residency_banned = contains(&policy.banned_residencies, normalized_residency.as_str());
+    if !check(
+        Rule::ResidencyBanList,
+        TraceValue::Residencies(policy.banned_residencies.clone()),
+        TraceValue::Residency(normalized_residency.clone()),
+        residency_valid && !residency_banned,
+    ) && residency_valid
+    {
+        violations.push(Violation::ResidencyBanned {
+            residency: normalized_residency.clone(),
+        });
+    }
+
+    if !check(
+        Rule::KycFloor,
+        TraceValue::Level(policy.min_kyc_level),
+        TraceValue::Level(request.kyc_level),
+        request.kyc_level >= policy.min_kyc_level,
+    ) {
+        violations.push(Violation::KycLevelTooLow {
+            required: policy.min_kyc_level,
+            provided: request.kyc_level,
+        });
+    }
+
+    if !check(
+        Rule::RiskCeiling,
+        TraceValue::Level(policy.max_risk_score),
+        TraceValue::Level(request.risk_score),
+        request.risk_score <= policy.max_risk_score,
+    ) {
+        violations.push(Violation::RiskScoreTooHigh {
+            limit: policy.max_risk_score,
+            provided: request.risk_score,
+        });
+    }
+
+    if !check(
+        Rule::Accreditation,
+        TraceValue::Flag(policy.require_accreditation),
+        TraceValue::Flag(request.holds(InvestorCategory::Accredited)),
+        !policy.require_accreditation || request.holds(InvestorCategory::Accredited),
+    ) {
+        violations.push(Violation::AccreditationRequired);
+    }
+
+    let type_allowed = policy.allowed_investor_types.is_empty()
+        || request
+            .investor_type
+            .is_some_and(|kind| policy.allowed_investor_types.contains(&kind));
+    if !check(
+        Rule::InvestorType,
+        TraceValue::InvestorTypes(policy.allowed_investor_types.clone()),
+        TraceValue::InvestorType(request.investor_type),
+        type_allowed,
+    ) {
+        violations.push(Violation::InvestorTypeNotAllowed {
+            provided: request.investor_type,
+        });
+    }
+
+    let missing_categories: Vec<InvestorCategory> = policy
+        .required_investor_categories
+        .iter()
+        .copied()
+        .filter(|&category| !request.holds(category))
+        .collect();
+    let held_categories = policy
+        .required_investor_categories
+        .iter()
+        .copied()
+        .filter(|&category| request.holds(category))
+        .collect();
+    if !check(
+        Rule::InvestorCategories,
+        TraceValue::InvestorCategories(policy.required_investor_categories.clone()),
+        TraceValue::InvestorCategories(held_categories),
+        missing_categories.is_empty(),
+    ) {
+        violations.push(Violation::InvestorCategoriesMissing {
+            missing: missing_categories,
+        });
+    }
+
+    let portfolio_exposure = request.portfolio_exposure();
+    let portfolio_cap = policy.portfolio_cap(request);
+    // What the user can still add here, after both this pool's cap and the portfolio cap.
+    let max_allocation = exposure_cap
+        .saturating_sub(request.exposure_musd)
+        .min(portfolio_cap.map_or(Amount::MAX, |cap| cap.saturating_sub(portfolio_exposure)));
+    // In partial mode the amount rules are checked against the clamped amount, rounded down
+    // to a whole lot. When nothing fits, the full request is checked so the denial still
+    // names the rule it hit.
+    let amount = if request.allow_partial {
+        let lot = policy
+            .lot_size
+            .filter(|_| rules.applies(Rule::LotSize))
+            .unwrap_or(Amount::ZERO);
+        match request
+            .requested_amount
+            .min(policy.max_amount(request, context))
+            .round_down_to(lot)
+        {
+            clamped if clamped.is_zero() || clamped < policy.min_amount(request) => {
+                request.requested_amount
+            }
+            clamped => clamped,
+        }
+    } else {
+        request.requested_amount
+    };
+
+    if !check(
+        Rule::SingleTradeLimit,
+        TraceValue::Amount(single_trade_limit),
+        TraceValue::Amount(amount),
+        amount <= single_trade_limit,
+    ) {
+        violations.push(Violation::SingleTradeLimitExceeded {
+            limit: single_trade_limit,
+            requested: amount,
+        });
+    }
+
+    let projected_exposure = if request.action.increases_exposure() {
+        request.exposure_musd.saturating_add(amount)
+    } else {
+        request.exposure_musd.saturating_sub(amount)
+    };
+    if !check(
+        Rule::ExposureCap,
+        TraceValue::Amount(exposure_cap),
+        TraceValue::Amount(projected_exposure),
+        projected_exposure <= exposure_cap,
+    ) {
+        violations.push(Violation::ExposureCapExceeded {
+            cap: exposure_cap,
+            projected: projected_exposure,
+        });
+    }
+
+    // Volume is every approved inflow (buys and deposits); sells and withdrawals neither
+    // consume it nor are checked against it.
+    let inflow = request.action.increases_exposure();
+    for window in &policy.volume_windows {
+        let projected = context
+            .window_used(window.period_days)
+            .saturating_add(amount);
+        if inflow
+            && !check(
+                Rule::VolumeWindow,
+                TraceValue::Amount(window.max_volume),
+                TraceValue::Amount(projected),
+                projected <= window.max_volume,
+            )
+        {
+            violations.push(Violation::VolumeLimitExceeded {
+                period_days: window.period_days,
+                limit: window.max_volume,
+                projected,
+            });
+        }
+    }
+
+    if let Some(cap) = portfolio_cap {
+        let projected = if inflow {
+            portfolio_exposure.saturating_add(amount)
+        } else {
+            portfolio_exposure.saturating_sub(amount)
+        };
+        if !check(
+            Rule::PortfolioCap,
+            TraceValue::Amount(cap),
+            TraceValue::Amount(projected),
+            projected <= cap,
+        ) {
+            violations.push(Violation::PortfolioCapExceeded { cap, projected });
+        }
+    }
+
+    // Only exits are locked; buying more restarts the lock-up instead.
+    let unlocks_at = policy.unlocks_at(context);
+    if policy.lockup_days.is_some()
+        && !inflow
+        && !check(
+            Rule::LockUp,
+            TraceValue::Timestamp(policy.lockup_end(context)),
+            TraceValue::Timestamp(Some(context.now)),
+            unlocks_at.is_none(),
+        )
+    {
+        if let Some(unlocks_at) = unlocks_at {
+            violations.push(Violation::LockedUp { unlocks_at });
+        }
+    }
+
+    // Existing holders keep trading once the pool is full; only new addresses are turned away.
+    if let Some(max_investors) = policy.max_investors {
+        if inflow
+            && !check(
+                Rule::InvestorCap,
+                TraceValue::Count(max_investors),
+                TraceValue::Count(context.investor_count),
+                context.existing_investor || context.investor_count < max_investors,
+            )
+        {
+            violations.push(Violation::InvestorCapReached { max_investors });
+        }
+    }
+
+    // Closing out a whole position is always allowed, whatever is left of it. Only the
+    // host-tracked position counts: `exposure_musd` is the client's word.
+    let full_exit = !inflow
+        && context
+            .position
+            .is_some_and(|position| !position.is_zero() && amount >= position);
+    if let Some(minimum) = policy.min_trade {
+        if !full_exit
+            && !check(
+                Rule::MinTrade,
+                TraceValue::Amount(minimum),
+                TraceValue::Amount(amount),
+                amount >= minimum,
+            )
+        {
+            violations.push(Violation::BelowMinTrade {
+                minimum,
+                requested: amount,
+            });
+        }
+    }
+
+    if let Some(lot_size) = policy.lot_size {
+        if !full_exit
+            && !check(
+                Rule::LotSize,
+                TraceValue::Amount(lot_size),
+                TraceValue::Amount(amount),
+                amount.is_multiple_of(lot_size),
+            )
+        {
+            violations.push(Violation::NotLotMultiple {
+                lot_size,
+                requested: amount,
+            });
+        }
+    }
+
+    if let Some(minimum) = policy.min_initial_investment {
+        if request.is_initial_investment()
+            && !check(
+                Rule::MinInitialInvestment,
+                TraceValue::Amount(minimum),
+                TraceValue::Amount(amount),
+                amount >= minimum,
+            )
+        {
+            violations.push(Violation::BelowMinInitialInvestment {
+                minimum,
+                requested: amount,
+            });
+        }
+    }
+
+    if !policy.trading_windows.is_empty()
+        && !check(
+            Rule::TradingWindow,
+            TraceValue::TradingWindows(policy.trading_windows.clone()),
+            TraceValue::Timestamp(Some(context.now)),
+            policy.in_trading_window(context.now),
+        )
+    {
+        violations.push(Violation::OutsideTradingWindow {
+            evaluated_at: context.now,
+        });
+    }
+
+    let today = Date::from_timestamp(context.now);
+    if !policy.holidays.is_empty()
+        && !check(
+            Rule::BusinessDay,
+            TraceValue::Dates(policy.holidays.clone()),
+            TraceValue::Date(today),
+            !policy.is_holiday(today),
+        )
+    {
+        violations.push(Violation::MarketHoliday { date: today });
+    }
+
+    if let Some(eligibility) = &policy.eligibility {
+        let admitted = eligibility.admits(request);
+        if !check(
+            Rule::Eligibility,
+            TraceValue::Expression(eligibility.clone()),
+            TraceValue::Flag(admitted),
+            admitted,
+        ) {
+            violations.push(Violation::EligibilityRuleFailed {
+                rule: eligibility.to_string(),
+            });
+        }
+    }
+
+    let allowed = violations.is_empty();
+    let approved_amount = if allowed { amount } else { Amount::ZERO };
+    let window_allowances = policy
+        .volume_windows
+        .iter()
+        .map(|window| {
+            let used = context.window_used(window.period_days);
+            let consumed = if inflow {
+                approved_amount
+            } else {
+                Amount::ZERO
+            };
+            WindowAllowance {
+                period_days: window.period_days,
+                limit: window.max_volume,
+                used,
+                remaining: window
+                    .max_volume
+                    .saturating_sub(used.saturating_add(consumed)),
+            }
+        })
+        .collect();
+    let reason = if allowed && approved_amount < request.requested_amount {
+        format!(
+            "{} pool partial approval: approved {} of {} mUSD requested; user may allocate up to {} mUSD more.",
+            policy.name, approved_amount, request.requested_amount, max_allocation
+        )
+    } else if allowed && !request.action.increases_exposure() {
+        format!(
+            "{} pool approval: user may withdraw {} mUSD.",
+            policy.name, approved_amount
+        )
+    } else if allowed {
+        format!(
+            "{} pool approval: user may allocate up to {} mUSD more (requested {}).",
+            policy.name, max_allocation, request.requested_amount
+        )
+    } else {
+        violations
+            .iter()
+            .map(|violation| violation.message(&policy.name))
+            .collect::<Vec<_>>()
+            .join(" | ")
+    };
+
+    let outcome = ComplianceOutcome {
+        user: request.user.clone(),
+        pool_id: request.pool_id.clone(),
+        action: request.action,
+        tier: tier.map(|tier| tier.name.clone()),
+        address_override: context.address_override,
+        allowed,
+        reason,
+        violations,
+        max_allocation,
+        requested_amount: request.requested_amount,
+        approved_amount,
+        exposure_musd: request.exposure_musd,
+        portfolio_exposure_musd: portfolio_exposure,
+        window_allowances,
+        unlocks_at,
+        evaluated_at: context.now,
+        policy_version: policy.version,
+        policy_hash: policy.policy_hash(),
+    };
+
+    (outcome, trace)
+}
+
+/// Caps for users at or above a KYC level (and, optionally, accredited).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+#[serde(deny_unknown_fields)]
+pub struct KycTier {
+    #[cfg_attr(feature = "openapi", schema(example = "enhanced"))]
+    pub name: String,
+    #[cfg_attr(feature = "openapi", schema(example = 3))]
+    pub min_kyc_level: u8,
+    #[serde(default)]
+    #[cfg_attr(feature = "openapi", schema(example = false))]
+    pub require_accreditation: bool,
+    #[cfg_attr(feature = "openapi", schema(example = "50000000000"))]
+    pub max_single_trade: Amount,
+    #[cfg_attr(feature = "openapi", schema(example = "150000000000"))]
+    pub max_total_exposure: Amount,
+    /// Replaces the policy's `max_portfolio_exposure` for users in this tier.
+    #[serde(default)]
+    #[cfg_attr(feature = "openapi", schema(example = "300000000000"))]
+    pub max_portfolio_exposure: Option<Amount>,
+}
+
+impl KycTier {
+    pub fn admits(&self, request: &ComplianceRequest) -> bool {
+        request.kyc_level >= self.min_kyc_level
+            && (!self.require_accreditation || request.holds(InvestorCategory::Accredited))
+    }
+}
+
+/// Share of the exposure cap granted to users up to a risk score, in basis points.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+#[serde(deny_unknown_fields)]
+pub struct RiskBand {
+    #[cfg_attr(feature = "openapi", schema(example = 3))]
+    pub max_risk_score: u8,
+    /// 10000 grants the full cap.
+    #[cfg_attr(feature = "openapi", schema(example = 7500, maximum = 10000))]
+    pub allocation_bps: u16,
+}
+
+impl RiskBand {
+    pub const FULL_BPS: u16 = 10_000;
+
+    pub fn scale(&self, cap: Amount) -> Amount {
+        cap.mul_bps(self.allocation_bps.min(Self::FULL_BPS))
+    }
+}
+
+fn contains(list: &[String], value: &str) -> bool {
+    list.iter().any(|entry| entry.eq_ignore_ascii_case(value))
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,749 @@
+use alloc::string::ToString;
+use alloc::vec;
+
+use super::*;
+use crate::calendar::Weekday;
+use crate::types::{PoolExposure, PoolId};
+
+/// A policy every `request()` passes, with room to spare under each cap.
+fn policy() -> PoolPolicy {
+    PoolPolicy {
+        policy_id: "gold".to_string(),
+        name: "Gold".to_string(),
+        version: 1,
+        effective_from: 0,
+        allowed_residencies: vec!["US".to_string(), "DE".to_string()],
+        banned_residencies: vec!["KP".to_string()],
+        max_single_trade: Amount::from_musd(50_000),
+        max_total_exposure: Amount::from_musd(150_000),
+        min_kyc_level: 2,
+        max_risk_score: 4,
+        require_accreditation: false,
+        allowed_investor_types: Vec::new(),
+        required_investor_categories: Vec::new(),
+        volume_windows: Vec::new(),
+        tiers: Vec::new(),
+        risk_bands: Vec::new(),
+        max_portfolio_exposure: None,
+        lockup_days: None,
+        max_investors: None,
+        min_trade: None,
+        lot_size: None,
+        min_initial_investment: None,
+        trading_windows: Vec::new(),
+        holiday_calendar: None,
+        holidays: Vec::new(),
+        eligibility: None,
+        actions: ActionRuleSets::default(),
+    }
+}
+
+fn request() -> ComplianceRequest {
+    ComplianceRequest {
+        user: "0x2111222233334444555566667777888899990000".to_string(),
+        pool_id: PoolId::new("gold"),
+        action: Action::Buy,
+        residency: "US".to_string(),
+        kyc_level: 2,
+        aml_passed: true,
+        accredited_investor: false,
+        investor_type: None,
+        investor_categories: Vec::new(),
+        exposure_musd: Amount::from_musd(20_000),
+        requested_amount: Amount::from_musd(10_000),
+        risk_score: 3,
+        allow_partial: false,
+        portfolio: Vec::new(),
+    }
+}
+
+fn overridden(address_override: AddressOverride) -> EvaluationContext {
+    EvaluationContext {
+        address_override: Some(address_override),
+        ..EvaluationContext::default()
+    }
+}
+
+fn rules_checked(trace: &[RuleCheck]) -> Vec<Rule> {
+    trace.iter().map(|check| check.rule).collect()
+}
+
+#[test]
+fn passing_request_is_allowed() {
+    let outcome = evaluate(&request(), &policy(), &EvaluationContext::default());
+    assert!(outcome.allowed, "{}", outcome.reason);
+    assert_eq!(outcome.approved_amount, Amount::from_musd(10_000));
+    assert_eq!(outcome.max_allocation, Amount::from_musd(130_000));
+}
+
+#[test]
+fn allow_override_skips_identity_rules() {
+    let mut policy = policy();
+    policy.require_accreditation = true;
+    policy.allowed_investor_types = vec![InvestorType::Entity];
+    policy.required_investor_categories = vec![InvestorCategory::QualifiedPurchaser];
+    let request = ComplianceRequest {
+        residency: "FR".to_string(),
+        kyc_level: 0,
+        risk_score: 9,
+        ..request()
+    };
+
+    let (outcome, trace) =
+        evaluate_with_trace(&request, &policy, &overridden(AddressOverride::Allow));
+    assert!(outcome.allowed, "{}", outcome.reason);
+    let checked = rules_checked(&trace);
+    for skipped in [
+        Rule::ResidencyAllowList,
+        Rule::KycFloor,
+        Rule::RiskCeiling,
+        Rule::Accreditation,
+        Rule::InvestorType,
+        Rule::InvestorCategories,
+    ] {
+        assert!(!checked.contains(&skipped), "{skipped:?} was checked");
+    }
+    assert_eq!(checked[0], Rule::AddressOverride);
+    assert!(trace[0].passed);
+}
+
+#[test]
+fn allow_override_keeps_aml_and_the_ban_list() {
+    let context = overridden(AddressOverride::Allow);
+
+    let aml_failed = ComplianceRequest {
+        aml_passed: false,
+        ..request()
+    };
+    let outcome = evaluate(&aml_failed, &policy(), &context);
+    assert!(!outcome.allowed);
+    assert_eq!(outcome.violations, vec![Violation::AmlFailed]);
+
+    let banned = ComplianceRequest {
+        residency: "kp".to_string(),
+        ..request()
+    };
+    let outcome = evaluate(&banned, &policy(), &context);
+    assert_eq!(
+        outcome.violations,
+        vec![Violation::ResidencyBanned {
+            residency: "KP".to_string()
+        }]
+    );
+
+    let missing = ComplianceRequest {
+        residency: String::new(),
+        ..request()
+    };
+    let outcome = evaluate(&missing, &policy(), &context);
+    assert_eq!(outcome.violations, vec![Violation::ResidencyMissing]);
+}
+
+#[test]
+fn allow_override_keeps_amount_caps() {
+    let request = ComplianceRequest {
+        requested_amount: Amount::from_musd(60_000),
+        ..request()
+    };
+    let outcome = evaluate(&request, &policy(), &overridden(AddressOverride::Allow));
+    assert_eq!(
+        outcome.violations,
+        vec![Violation::SingleTradeLimitExceeded {
+            limit: Amount::from_musd(50_000),
+            requested: Amount::from_musd(60_000),
+        }]
+    );
+}
+
+#[test]
+fn deny_override_denies_and_is_traced() {
+    let (outcome, trace) =
+        evaluate_with_trace(&request(), &policy(), &overridden(AddressOverride::Deny));
+    assert!(!outcome.allowed);
+    assert_eq!(outcome.violations, vec![Violation::AddressDenied]);
+    assert_eq!(outcome.approved_amount, Amount::ZERO);
+    assert_eq!(
+        trace[0],
+        RuleCheck {
+            rule: Rule::AddressOverride,
+            threshold: TraceValue::Flag(false),
+            actual: TraceValue::Flag(true),
+            passed: false,
+        }
+    );
+}
+
+#[test]
+fn deny_override_applies_to_exits() {
+    let sell = ComplianceRequest {
+        action: Action::Sell,
+        ..request()
+    };
+    let (outcome, trace) =
+        evaluate_with_trace(&sell, &policy(), &overridden(AddressOverride::Deny));
+    assert_eq!(outcome.violations, vec![Violation::AddressDenied]);
+    assert_eq!(trace[0].rule, Rule::AddressOverride);
+}
+
+#[test]
+fn no_override_leaves_it_out_of_the_trace() {
+    let (_, trace) = evaluate_with_trace(&request(), &policy(), &EvaluationContext::default());
+    assert!(!rules_checked(&trace).contains(&Rule::AddressOverride));
+}
+
+fn tier(name: &str, min_kyc_level: u8, single: u64, total: u64) -> KycTier {
+    KycTier {
+        name: name.to_string(),
+        min_kyc_level,
+        require_accreditation: false,
+        max_single_trade: Amount::from_musd(single),
+        max_total_exposure: Amount::from_musd(total),
+        max_portfolio_exposure: None,
+    }
+}
+
+fn tiered_policy() -> PoolPolicy {
+    PoolPolicy {
+        min_kyc_level: 1,
+        tiers: vec![
+            tier("standard", 2, 10_000, 50_000),
+            tier("enhanced", 3, 50_000, 150_000),
+            KycTier {
+                require_accreditation: true,
+                ..tier("accredited", 3, 200_000, 500_000)
+            },
+        ],
+        ..policy()
+    }
+}
+
+#[test]
+fn highest_reached_tier_replaces_the_caps() {
+    let policy = tiered_policy();
+    let request = ComplianceRequest {
+        requested_amount: Amount::from_musd(20_000),
+        ..request()
+    };
+
+    let outcome = evaluate(&request, &policy, &EvaluationContext::default());
+    assert_eq!(outcome.tier.as_deref(), Some("standard"));
+    assert_eq!(
+        outcome.violations,
+        vec![Violation::SingleTradeLimitExceeded {
+            limit: Amount::from_musd(10_000),
+            requested: Amount::from_musd(20_000),
+        }]
+    );
+    assert_eq!(outcome.max_allocation, Amount::from_musd(30_000));
+
+    let enhanced = ComplianceRequest {
+        kyc_level: 3,
+        ..request
+    };
+    let outcome = evaluate(&enhanced, &policy, &EvaluationContext::default());
+    assert_eq!(outcome.tier.as_deref(), Some("enhanced"));
+    assert!(outcome.allowed, "{}", outcome.reason);
+    assert_eq!(outcome.max_allocation, Amount::from_musd(130_000));
+}
+
+#[test]
+fn accreditation_breaks_a_tie_between_tiers() {
+    let policy = tiered_policy();
+    let request = ComplianceRequest {
+        kyc_level: 3,
+        accredited_investor: true,
+        requested_amount: Amount::from_musd(100_000),
+        ..request()
+    };
+
+    let outcome = evaluate(&request, &policy, &EvaluationContext::default());
+    assert_eq!(outcome.tier.as_deref(), Some("accredited"));
+    assert!(outcome.allowed, "{}", outcome.reason);
+    assert_eq!(outcome.max_allocation, Amount::from_musd(480_000));
+
+    // Accreditation alone does not lift a user into a tier above their KYC level.
+    let below = ComplianceRequest {
+        kyc_level: 2,
+        ..request
+    };
+    let outcome = evaluate(&below, &policy, &EvaluationContext::default());
+    assert_eq!(outcome.tier.as_deref(), Some("standard"));
+}
+
+#[test]
+fn users_below_every_tier_keep_the_policy_caps() {
+    let request = ComplianceRequest {
+        kyc_level: 1,
+        requested_amount: Amount::from_musd(40_000),
+        ..request()
+    };
+
+    let outcome = evaluate(&request, &tiered_policy(), &EvaluationContext::default());
+    assert_eq!(outcome.tier, None);
+    assert!(outcome.allowed, "{}", outcome.reason);
+    assert_eq!(outcome.max_allocation, Amount::from_musd(130_000));
+}
+
+fn banded_policy() -> PoolPolicy {
+    PoolPolicy {
+        max_single_trade: Amount::from_musd(200_000),
+        risk_bands: vec![
+            RiskBand {
+                max_risk_score: 1,
+                allocation_bps: 10_000,
+            },
+            RiskBand {
+                max_risk_score: 3,
+                allocation_bps: 7_500,
+            },
+        ],
+        ..policy()
+    }
+}
+
+#[test]
+fn first_covering_risk_band_scales_the_exposure_cap() {
+    let request = ComplianceRequest {
+        risk_score: 2,
+        requested_amount: Amount::from_musd(100_000),
+        ..request()
+    };
+
+    let outcome = evaluate(&request, &banded_policy(), &EvaluationContext::default());
+    assert_eq!(
+        outcome.violations,
+        vec![Violation::ExposureCapExceeded {
+            cap: Amount::from_musd(112_500),
+            projected: Amount::from_musd(120_000),
+        }]
+    );
+    assert_eq!(outcome.max_allocation, Amount::from_musd(92_500));
+
+    let low_risk = ComplianceRequest {
+        risk_score: 1,
+        ..request
+    };
+    let outcome = evaluate(&low_risk, &banded_policy(), &EvaluationContext::default());
+    assert!(outcome.allowed, "{}", outcome.reason);
+    assert_eq!(outcome.max_allocation, Amount::from_musd(130_000));
+}
+
+#[test]
+fn risk_score_above_every_band_keeps_the_full_cap() {
+    let request = ComplianceRequest {
+        risk_score: 4,
+        requested_amount: Amount::from_musd(100_000),
+        ..request()
+    };
+
+    let policy = banded_policy();
+    assert_eq!(policy.risk_band_for(&request), None);
+    let outcome = evaluate(&request, &policy, &EvaluationContext::default());
+    assert!(outcome.allowed, "{}", outcome.reason);
+    assert_eq!(outcome.approved_amount, Amount::from_musd(100_000));
+    assert_eq!(outcome.max_allocation, Amount::from_musd(130_000));
+}
+
+fn position(pool_id: &str, musd: u64) -> PoolExposure {
+    PoolExposure {
+        pool_id: PoolId::new(pool_id),
+        exposure_musd: Amount::from_musd(musd),
+    }
+}
+
+#[test]
+fn portfolio_cap_sums_exposure_across_pools() {
+    let policy = PoolPolicy {
+        max_portfolio_exposure: Some(Amount::from_musd(70_000)),
+        ..policy()
+    };
+    // The entry for the requested pool is ignored in favour of `exposure_musd`, whatever
+    // the case it is named in.
+    let request = ComplianceRequest {
+        portfolio: vec![
+            position("silver", 20_000),
+            position("money_market", 10_000),
+            position("GOLD", 90_000),
+        ],
+        ..request()
+    };
+    assert_eq!(request.portfolio_exposure(), Amount::from_musd(50_000));
+
+    let outcome = evaluate(&request, &policy, &EvaluationContext::default());
+    assert!(outcome.allowed, "{}", outcome.reason);
+    assert_eq!(outcome.portfolio_exposure_musd, Amount::from_musd(50_000));
+    assert_eq!(outcome.max_allocation, Amount::from_musd(20_000));
+
+    let over = ComplianceRequest {
+        requested_amount: Amount::from_musd(30_000),
+        ..request
+    };
+    let outcome = evaluate(&over, &policy, &EvaluationContext::default());
+    assert_eq!(
+        outcome.violations,
+        vec![Violation::PortfolioCapExceeded {
+            cap: Amount::from_musd(70_000),
+            projected: Amount::from_musd(80_000),
+        }]
+    );
+}
+
+#[test]
+fn tier_portfolio_cap_replaces_the_policy_cap() {
+    let policy = PoolPolicy {
+        max_portfolio_exposure: Some(Amount::from_musd(70_000)),
+        tiers: vec![KycTier {
+            max_portfolio_exposure: Some(Amount::from_musd(200_000)),
+            ..tier("enhanced", 3, 50_000, 150_000)
+        }],
+        ..policy()
+    };
+    let request = ComplianceRequest {
+        kyc_level: 3,
+        requested_amount: Amount::from_musd(30_000),
+        portfolio: vec![position("silver", 30_000)],
+        ..request()
+    };
+
+    let outcome = evaluate(&request, &policy, &EvaluationContext::default());
+    assert!(outcome.allowed, "{}", outcome.reason);
+    assert_eq!(outcome.max_allocation, Amount::from_musd(130_000));
+}
+
+#[test]
+fn investor_type_must_be_allowed() {
+    let policy = PoolPolicy {
+        allowed_investor_types: vec![InvestorType::Entity],
+        ..policy()
+    };
+
+    let entity = ComplianceRequest {
+        investor_type: Some(InvestorType::Entity),
+        ..request()
+    };
+    let outcome = evaluate(&entity, &policy, &EvaluationContext::default());
+    assert!(outcome.allowed, "{}", outcome.reason);
+
+    let individual = ComplianceRequest {
+        investor_type: Some(InvestorType::Individual),
+        ..request()
+    };
+    let outcome = evaluate(&individual, &policy, &EvaluationContext::default());
+    assert_eq!(
+        outcome.violations,
+        vec![Violation::InvestorTypeNotAllowed {
+            provided: Some(InvestorType::Individual)
+        }]
+    );
+
+    // A pool that restricts types turns away requests that report none.
+    let (outcome, trace) = evaluate_with_trace(&request(), &policy, &EvaluationContext::default());
+    assert_eq!(
+        outcome.violations,
+        vec![Violation::InvestorTypeNotAllowed { provided: None }]
+    );
+    let check = trace
+        .iter()
+        .find(|check| check.rule == Rule::InvestorType)
+        .unwrap();
+    assert_eq!(
+        check.threshold,
+        TraceValue::InvestorTypes(vec![InvestorType::Entity])
+    );
+    assert_eq!(check.actual, TraceValue::InvestorType(None));
+}
+
+#[test]
+fn every_required_investor_category_must_be_held() {
+    let policy = PoolPolicy {
+        required_investor_categories: vec![
+            InvestorCategory::Accredited,
+            InvestorCategory::QualifiedPurchaser,
+        ],
+        ..policy()
+    };
+    let accredited = ComplianceRequest {
+        accredited_investor: true,
+        ..request()
+    };
+
+    let (outcome, trace) = evaluate_with_trace(&accredited, &policy, &EvaluationContext::default());
+    assert_eq!(
+        outcome.violations,
+        vec![Violation::InvestorCategoriesMissing {
+            missing: vec![InvestorCategory::QualifiedPurchaser]
+        }]
+    );
+    let check = trace
+        .iter()
+        .find(|check| check.rule == Rule::InvestorCategories)
+        .unwrap();
+    assert_eq!(
+        check.actual,
+        TraceValue::InvestorCategories(vec![InvestorCategory::Accredited])
+    );
+
+    let both = ComplianceRequest {
+        investor_categories: vec![InvestorCategory::QualifiedPurchaser],
+        ..accredited
+    };
+    let outcome = evaluate(&both, &policy, &EvaluationContext::default());
+    assert!(outcome.allowed, "{}", outcome.reason);
+}
+
+#[test]
+fn accredited_category_counts_as_accreditation() {
+    let policy = PoolPolicy {
+        require_accreditation: true,
+        ..policy()
+    };
+
+    let outcome = evaluate(&request(), &policy, &EvaluationContext::default());
+    assert_eq!(outcome.violations, vec![Violation::AccreditationRequired]);
+
+    let listed = ComplianceRequest {
+        investor_categories: vec![InvestorCategory::Accredited],
+        ..request()
+    };
+    let outcome = evaluate(&listed, &policy, &EvaluationContext::default());
+    assert!(outcome.allowed, "{}", outcome.reason);
+}
+
+const DAY: u64 = 86_400;
+
+#[test]
+fn lock_up_blocks_exits_until_it_ends() {
+    let policy = PoolPolicy {
+        lockup_days: Some(365),
+        ..policy()
+    };
+    let sell = ComplianceRequest {
+        action: Action::Sell,
+        ..request()
+    };
+    let acquired_at = 1_767_225_600;
+    let unlocks_at = acquired_at + 365 * DAY;
+    let context = |now| EvaluationContext {
+        last_acquired_at: Some(acquired_at),
+        now,
+        ..EvaluationContext::default()
+    };
+
+    let outcome = evaluate(&sell, &policy, &context(unlocks_at - 1));
+    assert_eq!(outcome.violations, vec![Violation::LockedUp { unlocks_at }]);
+    assert_eq!(outcome.unlocks_at, Some(unlocks_at));
+
+    let outcome = evaluate(&sell, &policy, &context(unlocks_at));
+    assert!(outcome.allowed, "{}", outcome.reason);
+    assert_eq!(outcome.unlocks_at, None);
+
+    // Buying more is never locked; it restarts the lock-up once recorded.
+    let outcome = evaluate(&request(), &policy, &context(unlocks_at - 1));
+    assert!(outcome.allowed, "{}", outcome.reason);
+}
+
+fn denominated_policy() -> PoolPolicy {
+    PoolPolicy {
+        min_trade: Some(Amount::from_musd(1_000)),
+        lot_size: Some(Amount::from_musd(500)),
+        min_initial_investment: Some(Amount::from_musd(25_000)),
+        ..policy()
+    }
+}
+
+#[test]
+fn trades_must_meet_the_minimum_and_lot_size() {
+    let policy = denominated_policy();
+    let small = ComplianceRequest {
+        requested_amount: Amount::from_musd(500),
+        ..request()
+    };
+    let outcome = evaluate(&small, &policy, &EvaluationContext::default());
+    assert_eq!(
+        outcome.violations,
+        vec![Violation::BelowMinTrade {
+            minimum: Amount::from_musd(1_000),
+            requested: Amount::from_musd(500),
+        }]
+    );
+
+    let odd = ComplianceRequest {
+        requested_amount: Amount::from_musd(1_250),
+        ..request()
+    };
+    let outcome = evaluate(&odd, &policy, &EvaluationContext::default());
+    assert_eq!(
+        outcome.violations,
+        vec![Violation::NotLotMultiple {
+            lot_size: Amount::from_musd(500),
+            requested: Amount::from_musd(1_250),
+        }]
+    );
+
+    let partial = ComplianceRequest {
+        allow_partial: true,
+        ..odd
+    };
+    let outcome = evaluate(&partial, &policy, &EvaluationContext::default());
+    assert!(outcome.allowed, "{}", outcome.reason);
+    assert_eq!(outcome.approved_amount, Amount::from_musd(1_000));
+}
+
+#[test]
+fn first_investment_must_meet_the_initial_minimum() {
+    let policy = denominated_policy();
+    let first = ComplianceRequest {
+        exposure_musd: Amount::ZERO,
+        ..request()
+    };
+    let outcome = evaluate(&first, &policy, &EvaluationContext::default());
+    assert_eq!(
+        outcome.violations,
+        vec![Violation::BelowMinInitialInvestment {
+            minimum: Amount::from_musd(25_000),
+            requested: Amount::from_musd(10_000),
+        }]
+    );
+
+    // Top-ups only need to meet the per-trade minimum.
+    let outcome = evaluate(&request(), &policy, &EvaluationContext::default());
+    assert!(outcome.allowed, "{}", outcome.reason);
+}
+
+#[test]
+fn full_exit_of_the_proven_position_skips_the_denominations() {
+    let policy = denominated_policy();
+    // The client claims the odd amount is their whole position.
+    let exit = ComplianceRequest {
+        action: Action::Sell,
+        exposure_musd: Amount::from_musd(750),
+        requested_amount: Amount::from_musd(750),
+        ..request()
+    };
+    let with_position = |position| EvaluationContext {
+        position,
+        ..EvaluationContext::default()
+    };
+
+    let outcome = evaluate(&exit, &policy, &with_position(None));
+    assert_eq!(
+        outcome.violations,
+        vec![
+            Violation::BelowMinTrade {
+                minimum: Amount::from_musd(1_000),
+                requested: Amount::from_musd(750),
+            },
+            Violation::NotLotMultiple {
+                lot_size: Amount::from_musd(500),
+                requested: Amount::from_musd(750),
+            },
+        ]
+    );
+
+    let outcome = evaluate(
+        &exit,
+        &policy,
+        &with_position(Some(Amount::from_musd(20_000))),
+    );
+    assert!(!outcome.allowed);
+
+    let outcome = evaluate(&exit, &policy, &with_position(Some(Amount::from_musd(750))));
+    assert!(outcome.allowed, "{}", outcome.reason);
+}
+
+/// Unix seconds of `time` (UTC) on `date`.
+fn at(date: &str, time: &str) -> u64 {
+    let date: Date = date.parse().unwrap();
+    let time: TimeOfDay = time.parse().unwrap();
+    date.start() + u64::from(time.0)
+}
+
+fn windowed_policy() -> PoolPolicy {
+    PoolPolicy {
+        trading_windows: vec![TradingWindow {
+            weekdays: vec![
+                Weekday::Monday,
+                Weekday::Tuesday,
+                Weekday::Wednesday,
+                Weekday::Thursday,
+                Weekday::Friday,
+            ],
+            opens: "09:00".parse().unwrap(),
+            closes: "14:00".parse().unwrap(),
+        }],
+        holidays: vec!["2026-01-19".parse().unwrap()],
+        ..policy()
+    }
+}
+
+#[test]
+fn trading_window_opens_inclusive_and_closes_exclusive() {
+    let policy = windowed_policy();
+    // 2026-01-05 is a Monday.
+    let context = |now| EvaluationContext {
+        now,
+        ..EvaluationContext::default()
+    };
+
+    for (time, open) in [
+        ("08:59", false),
+        ("09:00", true),
+        ("13:59", true),
+        ("14:00", false),
+    ] {
+        let now = at("2026-01-05", time);
+        assert_eq!(policy.in_trading_window(now), open, "{time}");
+        let outcome = evaluate(&request(), &policy, &context(now));
+        assert_eq!(outcome.allowed, open, "{time}: {}", outcome.reason);
+    }
+
+    let outcome = evaluate(&request(), &policy, &context(at("2026-01-05", "14:00")));
+    assert_eq!(
+        outcome.violations,
+        vec![Violation::OutsideTradingWindow {
+            evaluated_at: at("2026-01-05", "14:00")
+        }]
+    );
+}
+
+#[test]
+fn trading_window_is_closed_on_other_weekdays() {
+    let policy = windowed_policy();
+    // 2026-01-10 is a Saturday; the pool next opens on Monday morning.
+    let saturday = at("2026-01-10", "10:00");
+    assert!(!policy.in_trading_window(saturday));
+    assert_eq!(
+        policy.next_trading_time(saturday),
+        Some(at("2026-01-12", "09:00"))
+    );
+    // After Friday's close the next opening also skips the weekend.
+    assert_eq!(
+        policy.next_trading_time(at("2026-01-09", "14:00")),
+        Some(at("2026-01-12", "09:00"))
+    );
+}
+
+#[test]
+fn holidays_close_the_pool_inside_its_window() {
+    let policy = windowed_policy();
+    // Martin Luther King Jr. Day, a Monday.
+    let now = at("2026-01-19", "10:00");
+    let outcome = evaluate(
+        &request(),
+        &policy,
+        &EvaluationContext {
+            now,
+            ..EvaluationContext::default()
+        },
+    );
+    assert_eq!(
+        outcome.violations,
+        vec![Violation::MarketHoliday {
+            date: "2026-01-19".parse().unwrap()
+        }]
+    );
+    assert_eq!(
+        policy.next_trading_time(now),
+        Some(at("2026-01-20", "09:00"))
+    );
+}
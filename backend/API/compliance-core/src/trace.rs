@@ -0,0 +1,118 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+use serde::{Deserialize, Serialize};
+
+use crate::amount::Amount;
+use crate::calendar::{Date, TradingWindow};
+use crate::eligibility::Eligibility;
+use crate::investor::{InvestorCategory, InvestorType};
+
+#[cfg(feature = "openapi")]
+use utoipa::ToSchema;
+
+/// Rules `evaluate` checks, in the order it checks them.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum Rule {
+    Aml,
+    ResidencyAllowList,
+    ResidencyBanList,
+    KycFloor,
+    RiskCeiling,
+    Accreditation,
+    InvestorType,
+    InvestorCategories,
+    SingleTradeLimit,
+    ExposureCap,
+    VolumeWindow,
+    PortfolioCap,
+    LockUp,
+    InvestorCap,
+    MinTrade,
+    LotSize,
+    MinInitialInvestment,
+    TradingWindow,
+    BusinessDay,
+    Eligibility,
+    /// The operator's override for the address, checked before every other rule and for
+    /// every action whatever its rule set, so it is not part of [`Rule::ALL`]. The check
+    /// compares whether the address is denied; `false` passes.
+    AddressOverride,
+}
+
+impl Rule {
+    /// Rules about who the user is that an address allowlisted by the operator skips.
+    ///
+    /// AML, the residency ban list and the eligibility expression are never skipped: an
+    /// allowlist must not override sanctions screening. Neither are the amount, timing and
+    /// capacity rules.
+    pub fn is_overridable(self) -> bool {
+        matches!(
+            self,
+            Rule::ResidencyAllowList
+                | Rule::KycFloor
+                | Rule::RiskCeiling
+                | Rule::Accreditation
+                | Rule::InvestorType
+                | Rule::InvestorCategories
+        )
+    }
+
+    pub const ALL: [Rule; 20] = [
+        Rule::Aml,
+        Rule::ResidencyAllowList,
+        Rule::ResidencyBanList,
+        Rule::KycFloor,
+        Rule::RiskCeiling,
+        Rule::Accreditation,
+        Rule::InvestorType,
+        Rule::InvestorCategories,
+        Rule::SingleTradeLimit,
+        Rule::ExposureCap,
+        Rule::VolumeWindow,
+        Rule::PortfolioCap,
+        Rule::LockUp,
+        Rule::InvestorCap,
+        Rule::MinTrade,
+        Rule::LotSize,
+        Rule::MinInitialInvestment,
+        Rule::TradingWindow,
+        Rule::BusinessDay,
+        Rule::Eligibility,
+    ];
+}
+
+/// A threshold or an observed value in a [`RuleCheck`].
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+#[serde(untagged)]
+pub enum TraceValue {
+    Flag(bool),
+    Level(u8),
+    Amount(Amount),
+    Residency(String),
+    Residencies(Vec<String>),
+    InvestorType(Option<InvestorType>),
+    InvestorTypes(Vec<InvestorType>),
+    InvestorCategories(Vec<InvestorCategory>),
+    /// Unix seconds; `null` when there is nothing to compare, e.g. no recorded purchase.
+    Timestamp(Option<u64>),
+    /// A number of investors.
+    Count(u32),
+    TradingWindows(Vec<TradingWindow>),
+    Date(Date),
+    Dates(Vec<Date>),
+    Expression(Eligibility),
+}
+
+/// Outcome of one rule: what the policy demanded, what the request provided, and whether
+/// the rule passed.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct RuleCheck {
+    pub rule: Rule,
+    pub threshold: TraceValue,
+    pub actual: TraceValue,
+    pub passed: bool,
+}
@@ -0,0 +1,354 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+use serde::{Deserialize, Serialize};
+
+use crate::action::Action;
+use crate::amount::Amount;
+use crate::investor::{InvestorCategory, InvestorType};
+use crate::policy::PoolPolicy;
+use crate::violation::Violation;
+use crate::volume::{WindowAllowance, WindowVolume};
+
+#[cfg(feature = "openapi")]
+use utoipa::ToSchema;
+
+/// Identifies a pool in the host's registry: either its registry key (e.g. `gold`) or the
+/// address of its on-chain `RWAPool`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+#[cfg_attr(feature = "openapi", schema(value_type = String, example = "gold"))]
+#[serde(transparent)]
+pub struct PoolId(pub String);
+
+impl PoolId {
+    pub fn new(id: impl Into<String>) -> Self {
+        Self(id.into())
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Keys and addresses are matched case-insensitively.
+    pub fn matches(&self, other: &str) -> bool {
+        self.0.eq_ignore_ascii_case(other)
+    }
+}
+
+impl fmt::Display for PoolId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// SHA-256 of a policy's canonical encoding, committed to the journal next to the decision.
+///
+/// Rendered as a `0x`-prefixed hex string in JSON and as raw bytes in the zkVM wire format.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct PolicyHash(pub [u8; 32]);
+
+impl fmt::Display for PolicyHash {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("0x")?;
+        for byte in self.0 {
+            write!(f, "{:02x}", byte)?;
+        }
+        Ok(())
+    }
+}
+
+impl core::str::FromStr for PolicyHash {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let digits = s.strip_prefix("0x").unwrap_or(s).as_bytes();
+        if digits.len() != 64 {
+            return Err("policy hash must be 32 bytes of hex");
+        }
+        let mut bytes = [0u8; 32];
+        for (byte, pair) in bytes.iter_mut().zip(digits.chunks(2)) {
+            let high = hex_value(pair[0]).ok_or("policy hash is not valid hex")?;
+            let low = hex_value(pair[1]).ok_or("policy hash is not valid hex")?;
+            *byte = (high << 4) | low;
+        }
+        Ok(Self(bytes))
+    }
+}
+
+fn hex_value(digit: u8) -> Option<u8> {
+    match digit {
+        b'0'..=b'9' => Some(digit - b'0'),
+        b'a'..=b'f' => Some(digit - b'a' + 10),
+        b'A'..=b'F' => Some(digit - b'A' + 10),
+        _ => None,
+    }
+}
+
+impl Serialize for PolicyHash {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.collect_str(self)
+        } else {
+            self.0.serialize(serializer)
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for PolicyHash {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            let hex = String::deserialize(deserializer)?;
+            hex.parse().map_err(serde::de::Error::custom)
+        } else {
+            <[u8; 32]>::deserialize(deserializer).map(Self)
+        }
+    }
+}
+
+/// Compliance payload submitted by the frontend.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+#[cfg_attr(
+    feature = "openapi",
+    schema(
+        example = json!({
+            "user": "0x2111222233334444555566667777888899990000",
+            "pool_id": "gold",
+            "action": "buy",
+            "residency": "US",
+            "kyc_level": 2,
+            "aml_passed": true,
+            "accredited_investor": true,
+            "investor_type": "individual",
+            "investor_categories": ["qualified_purchaser"],
+            "exposure_musd": "20000000000",
+            "requested_amount": "10000000000",
+            "risk_score": 3,
+            "portfolio": [{"pool_id": "money_market", "exposure_musd": "40000000000"}]
+        })
+    )
+)]
+pub struct ComplianceRequest {
+    #[cfg_attr(
+        feature = "openapi",
+        schema(example = "0x2111222233334444555566667777888899990000")
+    )]
+    pub user: String,
+    #[cfg_attr(feature = "openapi", schema(example = "gold"))]
+    pub pool_id: PoolId,
+    /// Defaults to `buy` for payloads that predate actions.
+    #[serde(default)]
+    pub action: Action,
+    #[cfg_attr(feature = "openapi", schema(example = "US"))]
+    pub residency: String,
+    #[cfg_attr(feature = "openapi", schema(example = 2, minimum = 0, maximum = 3))]
+    pub kyc_level: u8,
+    #[cfg_attr(feature = "openapi", schema(example = true))]
+    pub aml_passed: bool,
+    #[cfg_attr(feature = "openapi", schema(example = true))]
+    pub accredited_investor: bool,
+    /// Unset when the identity provider did not report one.
+    #[serde(default)]
+    #[cfg_attr(feature = "openapi", schema(example = "individual"))]
+    pub investor_type: Option<InvestorType>,
+    /// Classifications the investor holds on top of `accredited_investor`, which counts as
+    /// `accredited`.
+    #[serde(default)]
+    #[cfg_attr(feature = "openapi", schema(example = json!(["qualified_purchaser"])))]
+    pub investor_categories: Vec<InvestorCategory>,
+    /// Current exposure in the requested pool, in mUSD base units.
+    #[cfg_attr(feature = "openapi", schema(example = "20000000000"))]
+    pub exposure_musd: Amount,
+    /// mUSD base units, exactly as passed to `RWAPool.swap` as `amountIn`.
+    #[cfg_attr(feature = "openapi", schema(example = "10000000000"))]
+    pub requested_amount: Amount,
+    #[cfg_attr(feature = "openapi", schema(example = 3, minimum = 0, maximum = 10))]
+    pub risk_score: u8,
+    /// Approve whatever part of `requested_amount` fits under the single-trade limit and the
+    /// remaining exposure instead of denying the whole request.
+    #[serde(default)]
+    #[cfg_attr(feature = "openapi", schema(example = false))]
+    pub allow_partial: bool,
+    /// The user's exposure in their other RWA pools; `exposure_musd` covers the requested
+    /// pool, and an entry for it here is ignored.
+    #[serde(default)]
+    pub portfolio: Vec<PoolExposure>,
+}
+
+impl ComplianceRequest {
+    /// Highest KYC level an identity provider assigns.
+    pub const MAX_KYC_LEVEL: u8 = 3;
+    /// Highest risk score an identity provider assigns.
+    pub const MAX_RISK_SCORE: u8 = 10;
+
+    pub fn holds(&self, category: InvestorCategory) -> bool {
+        (category == InvestorCategory::Accredited && self.accredited_investor)
+            || self.investor_categories.contains(&category)
+    }
+
+    /// Whether this is the user's first buy or deposit into the pool.
+    pub fn is_initial_investment(&self) -> bool {
+        self.action.increases_exposure() && self.exposure_musd.is_zero()
+    }
+
+    /// Exposure across every pool, the requested one included, before this request.
+    pub fn portfolio_exposure(&self) -> Amount {
+        self.portfolio
+            .iter()
+            .filter(|position| !position.pool_id.matches(self.pool_id.as_str()))
+            .fold(self.exposure_musd, |total, position| {
+                total.saturating_add(position.exposure_musd)
+            })
+    }
+}
+
+/// A user's current mUSD exposure in one pool.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct PoolExposure {
+    #[cfg_attr(feature = "openapi", schema(example = "money_market"))]
+    pub pool_id: PoolId,
+    #[cfg_attr(feature = "openapi", schema(example = "40000000000"))]
+    pub exposure_musd: Amount,
+}
+
+/// Operator decision pinned to one wallet, either for one pool or for every pool.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum AddressOverride {
+    /// Skip the residency allow-list, KYC, risk and investor classification rules; AML, the
+    /// residency ban list, the eligibility expression and the amount caps still apply.
+    Allow,
+    /// Deny every request, whatever the rules say.
+    Deny,
+}
+
+/// State the host tracks on the user's behalf, evaluated next to the client-supplied request.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct EvaluationContext {
+    /// Volume already approved for the user in each of the policy's rolling windows. A window
+    /// with no entry counts as unused.
+    pub window_volumes: Vec<WindowVolume>,
+    /// Override the operator set for the user's address, if any.
+    pub address_override: Option<AddressOverride>,
+    /// Unix seconds of the user's most recent purchase in the pool, if one is recorded.
+    pub last_acquired_at: Option<u64>,
+    /// Unix seconds the request is evaluated at; lock-ups are measured against it.
+    pub now: u64,
+    /// Distinct addresses holding a position in the pool.
+    pub investor_count: u32,
+    /// Whether the user is one of them, and so does not take up a new slot.
+    pub existing_investor: bool,
+    /// The user's position in the pool as proven by earlier approvals, if the host has one on
+    /// record. Exits of all of it are exempt from the trade size floors.
+    pub position: Option<Amount>,
+}
+
+impl EvaluationContext {
+    pub fn window_used(&self, period_days: u32) -> Amount {
+        self.window_volumes
+            .iter()
+            .find(|window| window.period_days == period_days)
+            .map_or(Amount::ZERO, |window| window.used)
+    }
+}
+
+/// Everything the guest reads from stdin: the request, the policy it is evaluated under and
+/// the host-tracked context.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ComplianceInput {
+    pub request: ComplianceRequest,
+    pub policy: PoolPolicy,
+    pub context: EvaluationContext,
+}
+
+/// Decision committed to the journal by the guest and returned to API callers.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+#[cfg_attr(
+    feature = "openapi",
+    schema(
+        example = json!({
+            "user": "0x2111222233334444555566667777888899990000",
+            "pool_id": "gold",
+            "action": "buy",
+            "tier": null,
+            "address_override": null,
+            "allowed": true,
+            "reason": "All compliance checks passed",
+            "violations": [],
+            "max_allocation": "50000000000",
+            "requested_amount": "10000000000",
+            "approved_amount": "10000000000",
+            "exposure_musd": "20000000000",
+            "portfolio_exposure_musd": "60000000000",
+            "window_allowances": [],
+            "unlocks_at": null,
+            "evaluated_at": 1767279600,
+            "policy_version": 1,
+            "policy_hash": "0x9f2c4e0d6b1a8f3e5c7d9b2a4f6e8c0d1b3a5f7e9c2d4b6a8f0e1c3d5b7a9f2c"
+        })
+    )
+)]
+pub struct ComplianceOutcome {
+    #[cfg_attr(
+        feature = "openapi",
+        schema(example = "0x2111222233334444555566667777888899990000")
+    )]
+    pub user: String,
+    #[cfg_attr(feature = "openapi", schema(example = "gold"))]
+    pub pool_id: PoolId,
+    pub action: Action,
+    /// Name of the KYC tier whose caps applied, if the policy declares tiers and the user
+    /// reached one.
+    #[cfg_attr(feature = "openapi", schema(example = json!(null)))]
+    pub tier: Option<String>,
+    /// Operator override that applied to the user's address, if any.
+    #[cfg_attr(feature = "openapi", schema(example = json!(null)))]
+    pub address_override: Option<AddressOverride>,
+    #[cfg_attr(feature = "openapi", schema(example = true))]
+    pub allowed: bool,
+    /// Rendered from `violations`, or the approval summary when allowed.
+    #[cfg_attr(feature = "openapi", schema(example = "All compliance checks passed"))]
+    pub reason: String,
+    /// Every rule the request failed; empty when allowed.
+    pub violations: Vec<Violation>,
+    #[cfg_attr(feature = "openapi", schema(example = "50000000000"))]
+    pub max_allocation: Amount,
+    #[cfg_attr(feature = "openapi", schema(example = "10000000000"))]
+    pub requested_amount: Amount,
+    /// Amount the proof approves: `requested_amount`, or less for a partial approval; zero
+    /// when denied.
+    #[cfg_attr(feature = "openapi", schema(example = "10000000000"))]
+    pub approved_amount: Amount,
+    #[cfg_attr(feature = "openapi", schema(example = "20000000000"))]
+    pub exposure_musd: Amount,
+    /// Exposure across every pool before the request, as used by the portfolio cap.
+    #[cfg_attr(feature = "openapi", schema(example = "60000000000"))]
+    pub portfolio_exposure_musd: Amount,
+    /// One entry per rolling window the policy declares; empty when it declares none.
+    pub window_allowances: Vec<WindowAllowance>,
+    /// Unix seconds at which the user's position in the pool unlocks, while a lock-up from
+    /// their last purchase is running.
+    #[cfg_attr(feature = "openapi", schema(example = json!(null)))]
+    pub unlocks_at: Option<u64>,
+    /// Unix seconds the request was evaluated at, from the host's clock. Committed so the
+    /// trading-window and lock-up checks can be audited against it.
+    #[cfg_attr(feature = "openapi", schema(example = 1767279600))]
+    pub evaluated_at: u64,
+    /// Version of the policy the decision was evaluated under; see [`PoolPolicy::version`].
+    #[cfg_attr(feature = "openapi", schema(example = 1))]
+    pub policy_version: u32,
+    /// Hash of the policy the decision was evaluated under; see [`PoolPolicy::policy_hash`].
+    #[cfg_attr(
+        feature = "openapi",
+        schema(
+            value_type = String,
+            example = "0x9f2c4e0d6b1a8f3e5c7d9b2a4f6e8c0d1b3a5f7e9c2d4b6a8f0e1c3d5b7a9f2c"
+        )
+    )]
+    pub policy_hash: PolicyHash,
+}
@@ -0,0 +1,224 @@
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use serde::{Deserialize, Serialize};
+
+use crate::amount::Amount;
+use crate::calendar::Date;
+use crate::investor::{InvestorCategory, InvestorType};
+
+#[cfg(feature = "openapi")]
+use utoipa::ToSchema;
+
+/// A reason a request failed its pool policy, with the values compared; see [`Violation::message`].
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum Violation {
+    AddressDenied,
+    AmlFailed,
+    ResidencyMissing,
+    ResidencyInvalid {
+        residency: String,
+    },
+    ResidencyNotAllowed {
+        residency: String,
+    },
+    ResidencyBanned {
+        residency: String,
+    },
+    KycLevelTooLow {
+        required: u8,
+        provided: u8,
+    },
+    RiskScoreTooHigh {
+        limit: u8,
+        provided: u8,
+    },
+    AccreditationRequired,
+    InvestorTypeNotAllowed {
+        provided: Option<InvestorType>,
+    },
+    InvestorCategoriesMissing {
+        missing: Vec<InvestorCategory>,
+    },
+    SingleTradeLimitExceeded {
+        limit: Amount,
+        requested: Amount,
+    },
+    ExposureCapExceeded {
+        cap: Amount,
+        projected: Amount,
+    },
+    VolumeLimitExceeded {
+        period_days: u32,
+        limit: Amount,
+        projected: Amount,
+    },
+    PortfolioCapExceeded {
+        cap: Amount,
+        projected: Amount,
+    },
+    LockedUp {
+        unlocks_at: u64,
+    },
+    InvestorCapReached {
+        max_investors: u32,
+    },
+    BelowMinTrade {
+        minimum: Amount,
+        requested: Amount,
+    },
+    NotLotMultiple {
+        lot_size: Amount,
+        requested: Amount,
+    },
+    BelowMinInitialInvestment {
+        minimum: Amount,
+        requested: Amount,
+    },
+    OutsideTradingWindow {
+        evaluated_at: u64,
+    },
+    MarketHoliday {
+        date: Date,
+    },
+    EligibilityRuleFailed {
+        rule: String,
+    },
+}
+
+impl Violation {
+    /// Stable machine-readable code, identical to the serialized variant name.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Violation::AddressDenied => "address_denied",
+            Violation::AmlFailed => "aml_failed",
+            Violation::ResidencyMissing => "residency_missing",
+            Violation::ResidencyInvalid { .. } => "residency_invalid",
+            Violation::ResidencyNotAllowed { .. } => "residency_not_allowed",
+            Violation::ResidencyBanned { .. } => "residency_banned",
+            Violation::KycLevelTooLow { .. } => "kyc_level_too_low",
+            Violation::RiskScoreTooHigh { .. } => "risk_score_too_high",
+            Violation::AccreditationRequired => "accreditation_required",
+            Violation::InvestorTypeNotAllowed { .. } => "investor_type_not_allowed",
+            Violation::InvestorCategoriesMissing { .. } => "investor_categories_missing",
+            Violation::SingleTradeLimitExceeded { .. } => "single_trade_limit_exceeded",
+            Violation::ExposureCapExceeded { .. } => "exposure_cap_exceeded",
+            Violation::VolumeLimitExceeded { .. } => "volume_limit_exceeded",
+            Violation::PortfolioCapExceeded { .. } => "portfolio_cap_exceeded",
+            Violation::LockedUp { .. } => "locked_up",
+            Violation::InvestorCapReached { .. } => "investor_cap_reached",
+            Violation::BelowMinTrade { .. } => "below_min_trade",
+            Violation::NotLotMultiple { .. } => "not_lot_multiple",
+            Violation::BelowMinInitialInvestment { .. } => "below_min_initial_investment",
+            Violation::OutsideTradingWindow { .. } => "outside_trading_window",
+            Violation::MarketHoliday { .. } => "market_holiday",
+            Violation::EligibilityRuleFailed { .. } => "eligibility_rule_failed",
+        }
+    }
+
+    /// Human-readable explanation for the pool named `pool`.
+    pub fn message(&self, pool: &str) -> String {
+        match self {
+            Violation::AddressDenied => {
+                format!("Address is blocked by the operator for {} pool", pool)
+            }
+            Violation::AmlFailed => "AML screening failed".to_string(),
+            Violation::ResidencyMissing => "Residency not provided".to_string(),
+            Violation::ResidencyInvalid { residency } => format!(
+                "Residency {} is not an ISO 3166-1 alpha-2 country code",
+                residency
+            ),
+            Violation::ResidencyNotAllowed { residency } => {
+                format!("{} pool is not open to residency {}", pool, residency)
+            }
+            Violation::ResidencyBanned { residency } => {
+                format!("Residency {} is blocked for {} pool", residency, pool)
+            }
+            Violation::KycLevelTooLow { required, provided } => format!(
+                "{} pool requires KYC level {} or higher (provided {})",
+                pool, required, provided
+            ),
+            Violation::RiskScoreTooHigh { limit, provided } => format!(
+                "Risk score {} exceeds {} pool limit {}",
+                provided, pool, limit
+            ),
+            Violation::AccreditationRequired => {
+                format!("{} pool is limited to accredited investors", pool)
+            }
+            Violation::InvestorTypeNotAllowed {
+                provided: Some(provided),
+            } => format!(
+                "{} pool is not open to {} investors",
+                pool,
+                provided.as_str()
+            ),
+            Violation::InvestorTypeNotAllowed { provided: None } => {
+                format!("{} pool requires the investor type", pool)
+            }
+            Violation::InvestorCategoriesMissing { missing } => format!(
+                "{} pool requires investor categories not held: {}",
+                pool,
+                missing
+                    .iter()
+                    .map(|category| category.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            Violation::SingleTradeLimitExceeded { limit, requested } => format!(
+                "Requested amount {} mUSD exceeds {} single-trade limit of {} mUSD",
+                requested, pool, limit
+            ),
+            Violation::ExposureCapExceeded { cap, projected } => format!(
+                "Projected exposure {} mUSD exceeds {} pool cap of {} mUSD",
+                projected, pool, cap
+            ),
+            Violation::VolumeLimitExceeded {
+                period_days,
+                limit,
+                projected,
+            } => format!(
+                "Projected {}-day volume {} mUSD exceeds {} pool limit of {} mUSD",
+                period_days, projected, pool, limit
+            ),
+            Violation::PortfolioCapExceeded { cap, projected } => format!(
+                "Projected RWA exposure {} mUSD across all pools exceeds the {} pool portfolio cap of {} mUSD",
+                projected, pool, cap
+            ),
+            Violation::LockedUp { unlocks_at } => format!(
+                "Position in {} pool is locked up until {} (Unix time)",
+                pool, unlocks_at
+            ),
+            Violation::InvestorCapReached { max_investors } => format!(
+                "{} pool has reached its cap of {} investors and only admits existing holders",
+                pool, max_investors
+            ),
+            Violation::BelowMinTrade { minimum, requested } => format!(
+                "Requested {} mUSD is below {} pool minimum trade {}",
+                requested, pool, minimum
+            ),
+            Violation::NotLotMultiple {
+                lot_size,
+                requested,
+            } => format!(
+                "Requested {} mUSD is not a multiple of {} pool lot size {}",
+                requested, pool, lot_size
+            ),
+            Violation::BelowMinInitialInvestment { minimum, requested } => format!(
+                "Requested {} mUSD is below {} pool minimum initial investment {}",
+                requested, pool, minimum
+            ),
+            Violation::OutsideTradingWindow { evaluated_at } => format!(
+                "{} pool is outside its trading windows at {} (Unix time)",
+                pool, evaluated_at
+            ),
+            Violation::MarketHoliday { date } => {
+                format!("{} pool is closed for the holiday on {}", pool, date)
+            }
+            Violation::EligibilityRuleFailed { rule } => {
+                format!("Request does not satisfy {} pool eligibility rule: {}", pool, rule)
+            }
+        }
+    }
+}
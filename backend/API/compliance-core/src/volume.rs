@@ -0,0 +1,53 @@
+use serde::{Deserialize, Serialize};
+
+use crate::amount::Amount;
+
+#[cfg(feature = "openapi")]
+use utoipa::ToSchema;
+
+pub const SECONDS_PER_DAY: u64 = 86_400;
+
+/// Cumulative cap on the volume a user may be approved for in one pool over a rolling window.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+#[serde(deny_unknown_fields)]
+pub struct VolumeWindow {
+    /// Length of the window, counted back from the time of the request.
+    #[cfg_attr(feature = "openapi", schema(example = 30))]
+    pub period_days: u32,
+    /// Most mUSD that may be approved inside one window.
+    #[cfg_attr(feature = "openapi", schema(example = "100000000000"))]
+    pub max_volume: Amount,
+}
+
+impl VolumeWindow {
+    pub fn period_secs(&self) -> u64 {
+        u64::from(self.period_days) * SECONDS_PER_DAY
+    }
+}
+
+/// Volume already approved for the user in one window, as recorded by the host.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct WindowVolume {
+    #[cfg_attr(feature = "openapi", schema(example = 30))]
+    pub period_days: u32,
+    #[cfg_attr(feature = "openapi", schema(example = "40000000000"))]
+    pub used: Amount,
+}
+
+/// What is left of one rolling window once the current request is accounted for.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct WindowAllowance {
+    #[cfg_attr(feature = "openapi", schema(example = 30))]
+    pub period_days: u32,
+    #[cfg_attr(feature = "openapi", schema(example = "100000000000"))]
+    pub limit: Amount,
+    /// Volume approved in the window before this request.
+    #[cfg_attr(feature = "openapi", schema(example = "40000000000"))]
+    pub used: Amount,
+    /// Volume still available after this request's `approved_amount`.
+    #[cfg_attr(feature = "openapi", schema(example = "50000000000"))]
+    pub remaining: Amount,
+}
@@ -0,0 +1,157 @@
+use anyhow::{ensure, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use utoipa::ToSchema;
+
+use crate::registry::is_address;
+use crate::types::PoolId;
+use crate::utils::write_atomically;
+
+pub const DEFAULT_ACQUISITIONS_PATH: &str = "data/acquisitions.json";
+
+/// A purchase of a pool's RWA token or a liquidity deposit, which starts the pool's lock-up
+/// for the user.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct Acquisition {
+    #[schema(example = "0x2111222233334444555566667777888899990000")]
+    pub user: String,
+    /// Registry key or `RWAPool` address; stored as the registry key.
+    #[schema(value_type = String, example = "real_estate")]
+    pub pool_id: PoolId,
+    /// Unix seconds, e.g. the block timestamp of the `Swap` event.
+    #[schema(example = 1767225600)]
+    pub acquired_at: u64,
+    /// Transaction of the indexed `Swap` event; unset for purchases recorded from proofs.
+    #[serde(default)]
+    #[schema(example = "0x5f1c0e2b7a9d4c3e8f6a1b2d3c4e5f60718293a4b5c6d7e8f90a1b2c3d4e5f60")]
+    pub tx_hash: Option<String>,
+}
+
+/// Latest purchase per user and pool, persisted as a JSON file so lock-ups survive restarts.
+///
+/// Only the latest purchase matters: a lock-up runs from the user's most recent buy or
+/// deposit and covers their whole position, since positions are not tracked in lots.
+#[derive(Debug, Clone)]
+pub struct AcquisitionStore {
+    path: PathBuf,
+    latest: Arc<Mutex<Vec<Acquisition>>>,
+}
+
+impl AcquisitionStore {
+    /// Opens the store at `path`, starting empty if the file does not exist yet.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let latest: Vec<Acquisition> = if path.exists() {
+            let raw = fs::read_to_string(&path)
+                .with_context(|| format!("failed to read acquisitions {}", path.display()))?;
+            serde_json::from_str(&raw)
+                .with_context(|| format!("invalid acquisitions {}", path.display()))?
+        } else {
+            Vec::new()
+        };
+        println!(
+            "✅ Loaded {} acquisitions from {}",
+            latest.len(),
+            path.display()
+        );
+
+        Ok(Self {
+            path,
+            latest: Arc::new(Mutex::new(latest)),
+        })
+    }
+
+    /// Unix seconds of `user`'s most recent purchase in `pool_id`.
+    pub fn last_acquired_at(&self, user: &str, pool_id: &PoolId) -> Option<u64> {
+        self.latest
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|entry| entry.user.eq_ignore_ascii_case(user) && entry.pool_id == *pool_id)
+            .map(|entry| entry.acquired_at)
+    }
+
+    /// Records a purchase, keeping the later one if the user already has one in the pool, so
+    /// events indexed out of order or twice are harmless. Returns the purchase now on record.
+    pub fn record(&self, mut acquisition: Acquisition, now: u64) -> Result<Acquisition> {
+        acquisition.user = acquisition.user.trim().to_ascii_lowercase();
+        ensure!(
+            is_address(&acquisition.user),
+            "{} is not a valid address",
+            acquisition.user
+        );
+        ensure!(
+            acquisition.acquired_at <= now,
+            "acquisition at {} is in the future",
+            acquisition.acquired_at
+        );
+
+        let mut latest = self.latest.lock().unwrap();
+        let existing = latest
+            .iter_mut()
+            .find(|entry| entry.user == acquisition.user && entry.pool_id == acquisition.pool_id);
+        let recorded = match existing {
+            Some(entry) if entry.acquired_at >= acquisition.acquired_at => return Ok(entry.clone()),
+            Some(entry) => {
+                *entry = acquisition;
+                entry.clone()
+            }
+            None => {
+                latest.push(acquisition.clone());
+                acquisition
+            }
+        };
+        write_atomically(&self.path, &serde_json::to_vec_pretty(&*latest)?)?;
+        Ok(recorded)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::temp_path;
+
+    const USER: &str = "0x2111222233334444555566667777888899990000";
+
+    fn acquisition(acquired_at: u64) -> Acquisition {
+        Acquisition {
+            user: USER.to_ascii_uppercase().replacen("0X", "0x", 1),
+            pool_id: PoolId::new("real_estate"),
+            acquired_at,
+            tx_hash: None,
+        }
+    }
+
+    #[test]
+    fn later_purchase_restarts_the_lock_up() {
+        let acquisitions = AcquisitionStore::load(temp_path("acquisitions-later")).unwrap();
+        let pool = PoolId::new("real_estate");
+
+        acquisitions.record(acquisition(100), 1_000).unwrap();
+        acquisitions.record(acquisition(500), 1_000).unwrap();
+        assert_eq!(acquisitions.last_acquired_at(USER, &pool), Some(500));
+
+        // Events indexed out of order do not move it back.
+        let recorded = acquisitions.record(acquisition(300), 1_000).unwrap();
+        assert_eq!(recorded.acquired_at, 500);
+        assert_eq!(acquisitions.last_acquired_at(USER, &pool), Some(500));
+
+        let reloaded = AcquisitionStore::load(&acquisitions.path).unwrap();
+        assert_eq!(reloaded.last_acquired_at(USER, &pool), Some(500));
+    }
+
+    #[test]
+    fn rejects_future_and_malformed_acquisitions() {
+        let acquisitions = AcquisitionStore::load(temp_path("acquisitions-invalid")).unwrap();
+
+        assert!(acquisitions.record(acquisition(2_000), 1_000).is_err());
+        let malformed = Acquisition {
+            user: "0x1234".to_string(),
+            ..acquisition(100)
+        };
+        assert!(acquisitions.record(malformed, 1_000).is_err());
+    }
+}
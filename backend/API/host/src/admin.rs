@@ -0,0 +1,205 @@
+use axum::{
+    extract::{Json, Path, Query, State},
+    http::{header::AUTHORIZATION, HeaderMap, StatusCode},
+};
+use serde::Deserialize;
+use std::sync::Arc;
+use utoipa::IntoParams;
+
+use crate::acquisition_store::Acquisition;
+use crate::overrides::OverrideEntry;
+use crate::shadow_store::ShadowReport;
+use crate::types::PoolId;
+use crate::utils::{unix_now, AppState};
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct OverrideScope {
+    /// Registry key or address of the pool; omit for the global override.
+    pool_id: Option<String>,
+}
+
+// GET /admin/overrides
+#[utoipa::path(
+    get,
+    path = "/admin/overrides",
+    tag = "Admin",
+    security(("admin_token" = [])),
+    responses(
+        (status = 200, description = "Every address override", body = [OverrideEntry]),
+        (status = 401, description = "Missing or wrong admin token")
+    )
+)]
+pub async fn get_overrides(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+) -> Result<Json<Vec<OverrideEntry>>, StatusCode> {
+    authorize(&state, &headers)?;
+    Ok(Json(state.overrides.all()))
+}
+
+// PUT /admin/overrides
+#[utoipa::path(
+    put,
+    path = "/admin/overrides",
+    tag = "Admin",
+    security(("admin_token" = [])),
+    request_body = OverrideEntry,
+    responses(
+        (status = 200, description = "Override stored; takes effect on the next request", body = OverrideEntry),
+        (status = 400, description = "Invalid address or missing reason"),
+        (status = 401, description = "Missing or wrong admin token"),
+        (status = 404, description = "Unknown pool")
+    )
+)]
+pub async fn put_override(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Json(mut entry): Json<OverrideEntry>,
+) -> Result<Json<OverrideEntry>, (StatusCode, String)> {
+    authorize(&state, &headers).map_err(|status| (status, String::new()))?;
+    if let Some(pool_id) = entry.pool_id.take() {
+        entry.pool_id = Some(pool_key(&state, pool_id.as_str()).ok_or((
+            StatusCode::NOT_FOUND,
+            format!("Pool {} is not registered", pool_id),
+        ))?);
+    }
+
+    let entry = state
+        .overrides
+        .upsert(entry, unix_now())
+        .map_err(|e| (StatusCode::BAD_REQUEST, e.to_string()))?;
+    println!(
+        "🛡️ Address override set: {} {:?} in {} ({})",
+        entry.address,
+        entry.kind,
+        entry.pool_id.as_ref().map_or("all pools", PoolId::as_str),
+        entry.reason
+    );
+    Ok(Json(entry))
+}
+
+// DELETE /admin/overrides/{address}
+#[utoipa::path(
+    delete,
+    path = "/admin/overrides/{address}",
+    tag = "Admin",
+    security(("admin_token" = [])),
+    params(
+        ("address" = String, Path, description = "Wallet address"),
+        OverrideScope
+    ),
+    responses(
+        (status = 204, description = "Override removed"),
+        (status = 401, description = "Missing or wrong admin token"),
+        (status = 404, description = "No such override")
+    )
+)]
+pub async fn delete_override(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Path(address): Path<String>,
+    Query(scope): Query<OverrideScope>,
+) -> Result<StatusCode, StatusCode> {
+    authorize(&state, &headers)?;
+    let pool_id = match scope.pool_id {
+        Some(pool_id) => Some(pool_key(&state, &pool_id).ok_or(StatusCode::NOT_FOUND)?),
+        None => None,
+    };
+
+    match state.overrides.remove(&address, pool_id.as_ref()) {
+        Ok(true) => {
+            println!("🛡️ Address override removed: {}", address);
+            Ok(StatusCode::NO_CONTENT)
+        }
+        Ok(false) => Err(StatusCode::NOT_FOUND),
+        Err(e) => {
+            eprintln!("❌ Failed to remove override: {:?}", e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+// POST /admin/acquisitions
+#[utoipa::path(
+    post,
+    path = "/admin/acquisitions",
+    tag = "Admin",
+    security(("admin_token" = [])),
+    request_body = Acquisition,
+    responses(
+        (status = 200, description = "The user's latest purchase in the pool, which starts its lock-up", body = Acquisition),
+        (status = 400, description = "Invalid address or a timestamp in the future"),
+        (status = 401, description = "Missing or wrong admin token"),
+        (status = 404, description = "Unknown pool")
+    )
+)]
+/// Records a buy indexed from an `RWAPool` `Swap` event (mUSD in, RWA token out).
+pub async fn post_acquisition(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Json(mut acquisition): Json<Acquisition>,
+) -> Result<Json<Acquisition>, (StatusCode, String)> {
+    authorize(&state, &headers).map_err(|status| (status, String::new()))?;
+    acquisition.pool_id = pool_key(&state, acquisition.pool_id.as_str()).ok_or((
+        StatusCode::NOT_FOUND,
+        format!("Pool {} is not registered", acquisition.pool_id),
+    ))?;
+
+    let recorded = state
+        .acquisitions
+        .record(acquisition, unix_now())
+        .map_err(|e| (StatusCode::BAD_REQUEST, e.to_string()))?;
+    println!(
+        "🔒 Acquisition recorded: {} in {} at {}",
+        recorded.user, recorded.pool_id, recorded.acquired_at
+    );
+    Ok(Json(recorded))
+}
+
+// GET /admin/shadow
+#[utoipa::path(
+    get,
+    path = "/admin/shadow",
+    tag = "Admin",
+    security(("admin_token" = [])),
+    responses(
+        (status = 200, description = "Per pool, how the candidate policy would have decided live requests: decisions flipped each way and the latest divergences", body = [ShadowReport]),
+        (status = 401, description = "Missing or wrong admin token")
+    )
+)]
+pub async fn get_shadow_reports(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+) -> Result<Json<Vec<ShadowReport>>, StatusCode> {
+    authorize(&state, &headers)?;
+    Ok(Json(state.shadow.all()))
+}
+
+/// Overrides are stored against the registry key, whichever way the pool was named.
+fn pool_key(state: &AppState, pool_id: &str) -> Option<PoolId> {
+    state
+        .registry
+        .pool(&PoolId::new(pool_id))
+        .map(|pool| pool.pool_id.clone())
+}
+
+fn authorize(state: &AppState, headers: &HeaderMap) -> Result<(), StatusCode> {
+    let expected = state
+        .admin_token
+        .as_deref()
+        .ok_or(StatusCode::UNAUTHORIZED)?;
+    let provided = headers
+        .get(AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .ok_or(StatusCode::UNAUTHORIZED)?;
+    if constant_time_eq(provided.as_bytes(), expected.as_bytes()) {
+        Ok(())
+    } else {
+        Err(StatusCode::UNAUTHORIZED)
+    }
+}
+
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |diff, (x, y)| diff | (x ^ y)) == 0
+}
@@ -1,4 +1,4 @@
-use crate::types::{ComplianceRequest, UserResponse};
+use crate::types::{ComplianceInput, Date, EvaluationContext, UserResponse};
 use once_cell::sync::Lazy;
 use std::collections::HashMap;
 use std::sync::Mutex;
@@ -6,29 +6,49 @@ use std::sync::Mutex;
 static RESPONSE_CACHE: Lazy<Mutex<HashMap<String, UserResponse>>> =
     Lazy::new(|| Mutex::new(HashMap::new()));
 
-pub fn get_cache_key(request: &ComplianceRequest) -> String {
+/// Keys on the policy hash and the host-tracked context too, so a retuned policy or newly
+/// recorded volume never serves a stale proof. The clock only enters through the lock-up
+/// expiry and whether the pool is open, so a cached proof stays valid until either changes.
+pub fn get_cache_key(input: &ComplianceInput) -> String {
+    let request = &input.request;
+    let context = EvaluationContext {
+        now: 0,
+        ..input.context.clone()
+    };
     format!(
-        "{}:{}:{}:{}:{}:{}:{}:{}:{}",
+        "{}:{}:{:?}:{}:{}:{}:{}:{:?}:{:?}:{}:{}:{}:{}:{:?}:{}:{:?}:{:?}:{}",
         request.user,
         request.pool_id,
+        request.action,
         request.residency,
         request.kyc_level,
         request.aml_passed,
         request.accredited_investor,
+        request.investor_type,
+        request.investor_categories,
         request.exposure_musd,
         request.requested_amount,
-        request.risk_score
+        request.risk_score,
+        request.allow_partial,
+        request.portfolio,
+        input.policy.policy_hash(),
+        context,
+        input.policy.unlocks_at(&input.context),
+        input.policy.in_trading_window(input.context.now)
+            && !input
+                .policy
+                .is_holiday(Date::from_timestamp(input.context.now))
     )
 }
 
-pub fn get_cached_response(request: &ComplianceRequest) -> Option<UserResponse> {
-    let key = get_cache_key(request);
+pub fn get_cached_response(input: &ComplianceInput) -> Option<UserResponse> {
+    let key = get_cache_key(input);
     let cache = RESPONSE_CACHE.lock().unwrap();
     cache.get(&key).cloned()
 }
 
-pub fn cache_response(request: &ComplianceRequest, response: &UserResponse) {
-    let key = get_cache_key(request);
+pub fn cache_response(input: &ComplianceInput, response: &UserResponse) {
+    let key = get_cache_key(input);
     let mut cache = RESPONSE_CACHE.lock().unwrap();
     cache.insert(key.clone(), response.clone());
     println!("✅ Cached response for key: {}", key);
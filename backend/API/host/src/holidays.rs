@@ -0,0 +1,48 @@
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use crate::types::Date;
+
+pub const DEFAULT_HOLIDAYS_PATH: &str = "policies/calendars/holidays.toml";
+
+/// Named holiday calendars policies reference through `holiday_calendar`.
+///
+/// Loaded from a TOML file with one list of dates per calendar:
+///
+/// ```toml
+/// [calendars]
+/// us_market = ["2026-01-01", "2026-12-25"]
+/// ```
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct HolidayCalendars {
+    #[serde(default)]
+    calendars: BTreeMap<String, Vec<Date>>,
+}
+
+impl HolidayCalendars {
+    /// Reads the calendars at `path`, or none if the file does not exist.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let raw = fs::read_to_string(path)
+            .with_context(|| format!("failed to read holiday calendars {}", path.display()))?;
+        let calendars: Self = toml::from_str(&raw)
+            .with_context(|| format!("invalid holiday calendars {}", path.display()))?;
+        println!(
+            "✅ Loaded {} holiday calendars from {}",
+            calendars.calendars.len(),
+            path.display()
+        );
+        Ok(calendars)
+    }
+
+    pub fn get(&self, name: &str) -> Option<&[Date]> {
+        self.calendars.get(name).map(Vec::as_slice)
+    }
+}
@@ -0,0 +1,155 @@
+use anyhow::{Context, Result};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use crate::types::PoolId;
+use crate::utils::write_atomically;
+
+pub const DEFAULT_INVESTORS_PATH: &str = "data/investors.json";
+
+/// Distinct holders per pool, persisted as a JSON file so investor caps survive restarts.
+///
+/// An address takes a slot when a buy or deposit is approved for it, before the proof, and
+/// gives it back if the proof fails. It leaves once an approved exit takes its position to
+/// zero, freeing its slot.
+#[derive(Debug, Clone)]
+pub struct InvestorStore {
+    path: PathBuf,
+    holders: Arc<Mutex<BTreeMap<PoolId, BTreeSet<String>>>>,
+}
+
+impl InvestorStore {
+    /// Opens the store at `path`, starting empty if the file does not exist yet.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let holders: BTreeMap<PoolId, BTreeSet<String>> = if path.exists() {
+            let raw = fs::read_to_string(&path)
+                .with_context(|| format!("failed to read investors {}", path.display()))?;
+            serde_json::from_str(&raw)
+                .with_context(|| format!("invalid investors {}", path.display()))?
+        } else {
+            BTreeMap::new()
+        };
+        println!(
+            "✅ Loaded holders of {} pools from {}",
+            holders.len(),
+            path.display()
+        );
+
+        Ok(Self {
+            path,
+            holders: Arc::new(Mutex::new(holders)),
+        })
+    }
+
+    /// Number of distinct holders in `pool_id`.
+    pub fn count(&self, pool_id: &PoolId) -> u32 {
+        self.holders
+            .lock()
+            .unwrap()
+            .get(pool_id)
+            .map_or(0, |holders| holders.len() as u32)
+    }
+
+    pub fn is_holder(&self, user: &str, pool_id: &PoolId) -> bool {
+        self.holders
+            .lock()
+            .unwrap()
+            .get(pool_id)
+            .is_some_and(|holders| holders.contains(&user.to_ascii_lowercase()))
+    }
+
+    /// Adds `user` to the holders of `pool_id` if there is a slot left under `max_investors`,
+    /// returning `None` when the pool is full and otherwise whether `user` took a new slot.
+    ///
+    /// The check and the admission happen under one lock, so two new addresses cannot both
+    /// take the last slot. The caller admits when it decides, before proving, and
+    /// [`InvestorStore::release`]s a newly taken slot if the proof does not go through.
+    pub fn admit(
+        &self,
+        user: &str,
+        pool_id: &PoolId,
+        max_investors: Option<u32>,
+    ) -> Result<Option<bool>> {
+        let mut holders = self.holders.lock().unwrap();
+        let pool = holders.entry(pool_id.clone()).or_default();
+        let user = user.to_ascii_lowercase();
+        if pool.contains(&user) {
+            return Ok(Some(false));
+        }
+        if max_investors.is_some_and(|max| pool.len() as u32 >= max) {
+            holders.retain(|_, pool| !pool.is_empty());
+            return Ok(None);
+        }
+        pool.insert(user);
+        self.persist(&holders).map(|()| Some(true))
+    }
+
+    /// Removes `user` from the holders of `pool_id`, freeing its slot.
+    pub fn release(&self, user: &str, pool_id: &PoolId) -> Result<()> {
+        let mut holders = self.holders.lock().unwrap();
+        let removed = holders
+            .get_mut(pool_id)
+            .is_some_and(|pool| pool.remove(&user.to_ascii_lowercase()));
+        if removed {
+            holders.retain(|_, pool| !pool.is_empty());
+            self.persist(&holders)?;
+        }
+        Ok(())
+    }
+
+    fn persist(&self, holders: &BTreeMap<PoolId, BTreeSet<String>>) -> Result<()> {
+        write_atomically(&self.path, &serde_json::to_vec_pretty(holders)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::temp_path;
+
+    #[test]
+    fn cap_holds_for_the_next_distinct_address() {
+        let investors = InvestorStore::load(temp_path("investors-cap")).unwrap();
+        let gold = PoolId::new("gold");
+
+        for user in ["0xa", "0xb", "0xc"] {
+            assert_eq!(investors.admit(user, &gold, Some(3)).unwrap(), Some(true));
+        }
+        assert_eq!(investors.admit("0xd", &gold, Some(3)).unwrap(), None);
+        assert_eq!(investors.count(&gold), 3);
+        assert!(!investors.is_holder("0xd", &gold));
+        // Existing holders keep trading once the pool is full.
+        assert_eq!(investors.admit("0xA", &gold, Some(3)).unwrap(), Some(false));
+        // Other pools have their own cap.
+        let silver = PoolId::new("silver");
+        assert_eq!(
+            investors.admit("0xd", &silver, Some(3)).unwrap(),
+            Some(true)
+        );
+    }
+
+    #[test]
+    fn released_slot_admits_the_next_address() {
+        let investors = InvestorStore::load(temp_path("investors-release")).unwrap();
+        let gold = PoolId::new("gold");
+
+        assert_eq!(investors.admit("0xa", &gold, Some(1)).unwrap(), Some(true));
+        assert_eq!(investors.admit("0xb", &gold, Some(1)).unwrap(), None);
+        investors.release("0xA", &gold).unwrap();
+        assert_eq!(investors.admit("0xb", &gold, Some(1)).unwrap(), Some(true));
+    }
+
+    #[test]
+    fn uncapped_pool_admits_everyone() {
+        let investors = InvestorStore::load(temp_path("investors-uncapped")).unwrap();
+        let gold = PoolId::new("gold");
+
+        for user in ["0xa", "0xb", "0xc"] {
+            assert_eq!(investors.admit(user, &gold, None).unwrap(), Some(true));
+        }
+        assert_eq!(investors.count(&gold), 3);
+    }
+}
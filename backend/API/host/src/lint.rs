@@ -0,0 +1,590 @@
+use std::fmt;
+
+use crate::types::{
+    Action, Amount, ComplianceRequest, Condition, InvestorCategory, InvestorType, LevelAttribute,
+    PoolPolicy, Rule,
+};
+
+/// How much a [`Finding`] matters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    /// Rules contradict each other, or no user or trade can pass them; never intended.
+    Error,
+    /// A setting that has no effect or is overridden by another, which may be intended.
+    Warning,
+}
+
+/// A problem the linter found in a policy.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Finding {
+    pub severity: Severity,
+    /// Names the policy and says what to change.
+    pub message: String,
+}
+
+impl fmt::Display for Finding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self.severity {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+        };
+        write!(f, "{}: {}", label, self.message)
+    }
+}
+
+/// Rules in `policy` that contradict each other or can never apply.
+///
+/// Runs on a validated policy, so residency groups are already expanded and tiers, bands and
+/// windows are sorted.
+pub fn lint(policy: &PoolPolicy) -> Vec<Finding> {
+    let mut findings = Vec::new();
+    let mut report = |severity: Severity, message: String| {
+        findings.push(Finding {
+            severity,
+            message: format!(
+                "policy {} version {}: {}",
+                policy.policy_id, policy.version, message
+            ),
+        });
+    };
+
+    let contradictory: Vec<&str> = policy
+        .allowed_residencies
+        .iter()
+        .filter(|code| policy.banned_residencies.contains(code))
+        .map(String::as_str)
+        .collect();
+    if !contradictory.is_empty() {
+        report(
+            Severity::Error,
+            format!(
+                "{} {} both allowed and banned; remove {} from one of the lists",
+                contradictory.join(", "),
+                if contradictory.len() == 1 {
+                    "is"
+                } else {
+                    "are"
+                },
+                if contradictory.len() == 1 {
+                    "it"
+                } else {
+                    "them"
+                }
+            ),
+        );
+        if contradictory.len() == policy.allowed_residencies.len() {
+            report(
+                Severity::Error,
+                "every allowed residency is banned, so no user can pass".to_string(),
+            );
+        }
+    }
+
+    if policy.min_kyc_level > ComplianceRequest::MAX_KYC_LEVEL {
+        report(
+            Severity::Error,
+            format!(
+                "min_kyc_level {} is above the highest KYC level {}, so no user can pass; lower it",
+                policy.min_kyc_level,
+                ComplianceRequest::MAX_KYC_LEVEL
+            ),
+        );
+    }
+
+    // Only entities can be regulated institutions.
+    if policy.allowed_investor_types == [InvestorType::Individual]
+        && policy
+            .required_investor_categories
+            .contains(&InvestorCategory::Institutional)
+    {
+        report(
+            Severity::Error,
+            "requires the institutional category but only admits individuals, so no user can \
+             pass; allow entities or drop the category"
+                .to_string(),
+        );
+    }
+
+    check_trade_fits(
+        &mut report,
+        "",
+        policy.max_single_trade,
+        policy.max_total_exposure,
+    );
+    if let Some(portfolio_cap) = policy.max_portfolio_exposure {
+        if portfolio_cap < policy.max_total_exposure {
+            report(
+                Severity::Warning,
+                format!(
+                    "max_total_exposure {} exceeds max_portfolio_exposure {}, so the pool cap \
+                     can never be reached; lower max_total_exposure or raise the portfolio cap",
+                    policy.max_total_exposure, portfolio_cap
+                ),
+            );
+        }
+    }
+
+    for (index, tier) in policy.tiers.iter().enumerate() {
+        let label = format!("tier {}: ", tier.name);
+        if tier.min_kyc_level > ComplianceRequest::MAX_KYC_LEVEL {
+            report(
+                Severity::Error,
+                format!(
+                    "{}min_kyc_level {} is above the highest KYC level {}, so no user reaches it",
+                    label,
+                    tier.min_kyc_level,
+                    ComplianceRequest::MAX_KYC_LEVEL
+                ),
+            );
+        }
+        check_trade_fits(
+            &mut report,
+            &label,
+            tier.max_single_trade,
+            tier.max_total_exposure,
+        );
+        // Tiers are sorted and the last one a user reaches applies, so an earlier tier with
+        // the same requirements is shadowed.
+        if let Some(next) = policy.tiers.get(index + 1).filter(|next| {
+            (next.min_kyc_level, next.require_accreditation)
+                == (tier.min_kyc_level, tier.require_accreditation)
+        }) {
+            report(
+                Severity::Warning,
+                format!(
+                    "{}has the same requirements as tier {}, which always applies instead; \
+                     merge them or change a min_kyc_level",
+                    label, next.name
+                ),
+            );
+        }
+    }
+
+    // A band only covers scores above the previous band's `max_risk_score`.
+    for pair in policy.risk_bands.windows(2) {
+        if pair[0].max_risk_score >= policy.max_risk_score {
+            report(
+                Severity::Warning,
+                format!(
+                    "the risk band up to score {} only covers scores above max_risk_score {}, \
+                     which are rejected before it applies; remove the band or raise \
+                     max_risk_score",
+                    pair[1].max_risk_score, policy.max_risk_score
+                ),
+            );
+        }
+    }
+
+    for window in &policy.volume_windows {
+        if window.max_volume < policy.max_single_trade {
+            report(
+                Severity::Warning,
+                format!(
+                    "the {}-day volume window allows {} mUSD, less than max_single_trade {}; \
+                     lower max_single_trade or raise the window",
+                    window.period_days, window.max_volume, policy.max_single_trade
+                ),
+            );
+        }
+    }
+    // Volume in a longer window always includes the volume in a shorter one.
+    for (index, short) in policy.volume_windows.iter().enumerate() {
+        if let Some(long) = policy.volume_windows[index + 1..]
+            .iter()
+            .find(|long| long.max_volume <= short.max_volume)
+        {
+            report(
+                Severity::Warning,
+                format!(
+                    "the {}-day window ({} mUSD) can never bind because the {}-day window only \
+                     allows {} mUSD; lower the {}-day limit or remove it",
+                    short.period_days,
+                    short.max_volume,
+                    long.period_days,
+                    long.max_volume,
+                    short.period_days
+                ),
+            );
+        }
+    }
+
+    if let Some(days) = policy.lockup_days {
+        if !Action::ALL.iter().any(|&action| {
+            !action.increases_exposure() && policy.rules_for(action).applies(Rule::LockUp)
+        }) {
+            report(
+                Severity::Warning,
+                format!(
+                    "lockup_days {} never applies because no sell-side action checks lock_up; \
+                     add it to the sell or remove_liquidity rules",
+                    days
+                ),
+            );
+        }
+    }
+
+    if let Some(minimum) = policy.min_trade {
+        if minimum > policy.max_single_trade {
+            report(
+                Severity::Error,
+                format!(
+                    "min_trade {} exceeds max_single_trade {}, so no trade can pass; lower \
+                     min_trade or raise max_single_trade",
+                    minimum, policy.max_single_trade
+                ),
+            );
+        }
+    }
+    if let Some(minimum) = policy.min_initial_investment {
+        if minimum > policy.max_total_exposure {
+            report(
+                Severity::Error,
+                format!(
+                    "min_initial_investment {} exceeds max_total_exposure {}, so no user can \
+                     invest; lower min_initial_investment or raise max_total_exposure",
+                    minimum, policy.max_total_exposure
+                ),
+            );
+        }
+    }
+    if let Some(lot) = policy.lot_size {
+        if lot > policy.max_single_trade {
+            report(
+                Severity::Error,
+                format!(
+                    "lot_size {} exceeds max_single_trade {}, so no trade can pass; lower \
+                     lot_size or raise max_single_trade",
+                    lot, policy.max_single_trade
+                ),
+            );
+        }
+        for (field, minimum) in [
+            ("min_trade", policy.min_trade),
+            ("min_initial_investment", policy.min_initial_investment),
+        ] {
+            if let Some(minimum) = minimum.filter(|minimum| !minimum.is_multiple_of(lot)) {
+                report(
+                    Severity::Warning,
+                    format!(
+                        "{} {} is not a multiple of lot_size {}, so the smallest trade that \
+                         passes is {}; make it a whole number of lots",
+                        field,
+                        minimum,
+                        lot,
+                        minimum.round_up_to(lot)
+                    ),
+                );
+            }
+        }
+    }
+
+    if let Some(max_investors) = policy.max_investors {
+        if !Action::ALL.iter().any(|&action| {
+            action.increases_exposure() && policy.rules_for(action).applies(Rule::InvestorCap)
+        }) {
+            report(
+                Severity::Warning,
+                format!(
+                    "max_investors {} never applies because no buy-side action checks \
+                     investor_cap; add it to the buy or add_liquidity rules",
+                    max_investors
+                ),
+            );
+        }
+    }
+
+    let checked = |rule| {
+        Action::ALL
+            .iter()
+            .any(|&action| policy.rules_for(action).applies(rule))
+    };
+    if !policy.trading_windows.is_empty() && !checked(Rule::TradingWindow) {
+        report(
+            Severity::Warning,
+            "trading_windows never apply because no action checks trading_window; add it to \
+             the rules of the actions it should gate"
+                .to_string(),
+        );
+    }
+    if !policy.holidays.is_empty() && !checked(Rule::BusinessDay) {
+        report(
+            Severity::Warning,
+            "holidays never apply because no action checks business_day; add it to the rules \
+             of the actions it should gate"
+                .to_string(),
+        );
+    }
+
+    if let Some(eligibility) = &policy.eligibility {
+        if !checked(Rule::Eligibility) {
+            report(
+                Severity::Warning,
+                "the eligibility expression never applies because no action checks \
+                 eligibility; add it to the rules of the actions it should gate"
+                    .to_string(),
+            );
+        }
+        match constant(&eligibility.0) {
+            Some(false) => report(
+                Severity::Error,
+                "the eligibility expression is never satisfied, so no request passes it; fix \
+                 or remove it"
+                    .to_string(),
+            ),
+            Some(true) => report(
+                Severity::Warning,
+                "the eligibility expression is always satisfied, so it never denies a request; \
+                 remove it"
+                    .to_string(),
+            ),
+            None => {}
+        }
+        eligibility.0.walk(&mut |condition| {
+            if let Condition::Level {
+                attribute, value, ..
+            } = condition
+            {
+                let max = level_max(*attribute);
+                if *value > max {
+                    report(
+                        Severity::Warning,
+                        format!(
+                            "the eligibility expression compares {} with {}, above the highest \
+                             value {}, so the comparison never varies; use a value up to {}",
+                            attribute.as_str(),
+                            value,
+                            max,
+                            max
+                        ),
+                    );
+                }
+            }
+        });
+    }
+
+    for action in Action::ALL {
+        let rules = policy.rules_for(action);
+        if let (Some(single), Some(total)) = (rules.max_single_trade, rules.max_total_exposure) {
+            check_trade_fits(&mut report, &format!("{:?} rules: ", action), single, total);
+        }
+    }
+
+    findings
+}
+
+fn check_trade_fits(
+    report: &mut impl FnMut(Severity, String),
+    label: &str,
+    single: Amount,
+    total: Amount,
+) {
+    if single > total {
+        report(
+            Severity::Error,
+            format!(
+                "{}max_single_trade {} exceeds max_total_exposure {}, so trades above {} can \
+                 never pass; lower max_single_trade to at most {}",
+                label, single, total, total, total
+            ),
+        );
+    }
+}
+
+fn level_max(attribute: LevelAttribute) -> u8 {
+    match attribute {
+        LevelAttribute::KycLevel => ComplianceRequest::MAX_KYC_LEVEL,
+        LevelAttribute::RiskScore => ComplianceRequest::MAX_RISK_SCORE,
+    }
+}
+
+/// The value `condition` takes for every request, if it never varies: constants, comparisons
+/// that hold for every KYC level or risk score or for none, and the `AND`, `OR` and `NOT` of
+/// those. `x OR false` and `NOT false` vary with `x` or hold, and so are not flagged.
+fn constant(condition: &Condition) -> Option<bool> {
+    match condition {
+        Condition::Const(value) => Some(*value),
+        Condition::Not(condition) => constant(condition).map(|value| !value),
+        Condition::All(conditions) => {
+            let values: Vec<Option<bool>> = conditions.iter().map(constant).collect();
+            if values.contains(&Some(false)) {
+                Some(false)
+            } else if values.iter().all(|value| *value == Some(true)) {
+                Some(true)
+            } else {
+                None
+            }
+        }
+        Condition::Any(conditions) => {
+            let values: Vec<Option<bool>> = conditions.iter().map(constant).collect();
+            if values.contains(&Some(true)) {
+                Some(true)
+            } else if values.iter().all(|value| *value == Some(false)) {
+                Some(false)
+            } else {
+                None
+            }
+        }
+        Condition::Level {
+            attribute,
+            comparison,
+            value,
+        } => {
+            let holds = |level: u8| comparison.compare(level, *value);
+            (1..=level_max(*attribute))
+                .all(|level| holds(level) == holds(0))
+                .then_some(holds(0))
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::policy;
+    use crate::types::{Amount, KycTier};
+
+    fn with_eligibility(expression: &str) -> PoolPolicy {
+        let mut policy = policy();
+        policy.eligibility = Some(expression.parse().unwrap());
+        policy
+    }
+
+    fn severities(policy: &PoolPolicy) -> Vec<Severity> {
+        lint(policy)
+            .into_iter()
+            .map(|finding| finding.severity)
+            .collect()
+    }
+
+    #[test]
+    fn clean_policy_has_no_findings() {
+        assert_eq!(lint(&policy()), []);
+    }
+
+    #[test]
+    fn satisfiable_expressions_with_constants_pass() {
+        for expression in [
+            "NOT false",
+            "accredited OR false",
+            "NOT (false AND accredited)",
+            "kyc_level >= 3 OR false",
+        ] {
+            let findings = lint(&with_eligibility(expression));
+            assert!(
+                findings
+                    .iter()
+                    .all(|finding| finding.severity == Severity::Warning),
+                "{expression}: {findings:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn unsatisfiable_expressions_are_errors() {
+        for expression in [
+            "false",
+            "accredited AND false",
+            "NOT (true OR accredited)",
+            "kyc_level > 3",
+            "risk_score < 0 AND aml_passed",
+        ] {
+            let findings = lint(&with_eligibility(expression));
+            assert_eq!(findings.len(), 1, "{expression}: {findings:?}");
+            assert_eq!(findings[0].severity, Severity::Error, "{expression}");
+            assert_eq!(
+                findings[0].message,
+                "policy gold version 1: the eligibility expression is never satisfied, so no \
+                 request passes it; fix or remove it"
+            );
+        }
+    }
+
+    #[test]
+    fn tautologies_and_out_of_range_levels_are_warnings() {
+        assert_eq!(
+            severities(&with_eligibility("accredited OR NOT false")),
+            [Severity::Warning]
+        );
+        assert_eq!(
+            severities(&with_eligibility("kyc_level <= 3")),
+            [Severity::Warning]
+        );
+        // `kyc_level > 5` never holds, but the expression still varies with `accredited`.
+        let findings = lint(&with_eligibility("accredited OR kyc_level > 5"));
+        assert_eq!(findings.len(), 1, "{findings:?}");
+        assert_eq!(findings[0].severity, Severity::Warning);
+        assert!(findings[0].message.contains("above the highest value 3"));
+    }
+
+    fn tier(min_kyc_level: u8, max_single_trade: u64) -> KycTier {
+        KycTier {
+            name: "enhanced".to_string(),
+            min_kyc_level,
+            require_accreditation: false,
+            max_single_trade: Amount::from_musd(max_single_trade),
+            max_total_exposure: Amount::from_musd(300_000),
+            max_portfolio_exposure: None,
+        }
+    }
+
+    #[test]
+    fn heuristic_cap_findings_are_warnings() {
+        let mut capped = policy();
+        capped.max_portfolio_exposure = Some(Amount::from_musd(100_000));
+        assert_eq!(severities(&capped), [Severity::Warning]);
+
+        let mut unused = policy();
+        unused.lockup_days = Some(365);
+        unused
+            .actions
+            .sell
+            .rules
+            .retain(|rule| *rule != Rule::LockUp);
+        unused
+            .actions
+            .remove_liquidity
+            .rules
+            .retain(|rule| *rule != Rule::LockUp);
+        assert_eq!(severities(&unused), [Severity::Warning]);
+    }
+
+    #[test]
+    fn rules_no_one_can_pass_are_errors() {
+        let mut contradictory = policy();
+        contradictory.banned_residencies = vec!["US".to_string()];
+        assert_eq!(severities(&contradictory), [Severity::Error]);
+
+        let mut unreachable = policy();
+        unreachable.min_kyc_level = 4;
+        assert_eq!(severities(&unreachable), [Severity::Error]);
+
+        let mut too_large = policy();
+        too_large.min_trade = Some(Amount::from_musd(60_000));
+        assert_eq!(severities(&too_large), [Severity::Error]);
+
+        let mut oversized = policy();
+        oversized.max_single_trade = Amount::from_musd(200_000);
+        assert_eq!(severities(&oversized), [Severity::Error]);
+    }
+
+    #[test]
+    fn tiers_no_one_can_pass_are_errors() {
+        let mut clean = policy();
+        clean.tiers = vec![tier(3, 100_000)];
+        assert_eq!(lint(&clean), []);
+
+        let mut unreachable = policy();
+        unreachable.tiers = vec![tier(4, 100_000)];
+        let findings = lint(&unreachable);
+        assert_eq!(findings.len(), 1, "{findings:?}");
+        assert_eq!(findings[0].severity, Severity::Error);
+        assert!(findings[0]
+            .message
+            .contains("tier enhanced: min_kyc_level 4 is above the highest KYC level 3"));
+
+        let mut oversized = policy();
+        oversized.tiers = vec![tier(3, 400_000)];
+        assert_eq!(severities(&oversized), [Severity::Error]);
+    }
+}
@@ -1,9 +1,10 @@
 use alloy::signers::local::PrivateKeySigner;
-use anyhow::Result;
+use anyhow::{bail, Result};
 use axum::response::IntoResponse;
 use axum::{
-    extract::{Json, State},
-    routing::{get, post},
+    extract::{Json, Path, State},
+    http::StatusCode,
+    routing::{delete, get, post},
     Router,
 };
 use k256::ecdsa::SigningKey;
@@ -11,22 +12,45 @@ use serde_json::json;
 use std::{env, sync::Arc};
 use tower_http::cors::{Any, CorsLayer};
 use url::Url;
-use utoipa::OpenApi;
+use utoipa::openapi::security::{Http, HttpAuthScheme, SecurityScheme};
+use utoipa::{Modify, OpenApi};
 use utoipa_swagger_ui::SwaggerUi;
 
+mod acquisition_store;
+mod admin;
 mod cache;
 mod elf_server;
+mod holidays;
+mod investor_store;
+mod lint;
+mod overrides;
 mod policy;
+mod position_store;
 mod proof_submitter;
+mod registry;
+mod remediation;
+mod shadow_store;
+#[cfg(test)]
+mod test_support;
 mod types;
 mod utils;
+mod volume_store;
 
+use crate::acquisition_store::*;
 use crate::cache::*;
 use crate::elf_server::serve_guest_elf;
+use crate::holidays::*;
+use crate::investor_store::*;
+use crate::overrides::*;
 use crate::policy::*;
+use crate::position_store::*;
 use crate::proof_submitter::*;
+use crate::registry::*;
+use crate::remediation::*;
+use crate::shadow_store::*;
 use crate::types::*;
 use crate::utils::*;
+use crate::volume_store::*;
 
 #[derive(OpenApi)]
 #[openapi(
@@ -40,6 +64,19 @@ use crate::utils::*;
         get_validate_user,
         post_validate_user_handler,
         post_compliance_pools_handler,
+        post_compliance_explain,
+        post_compliance_remediate,
+        get_pools,
+        get_pool,
+        get_pool_policies,
+        get_policies,
+        get_policy,
+        get_jurisdictions,
+        admin::get_overrides,
+        admin::put_override,
+        admin::delete_override,
+        admin::post_acquisition,
+        admin::get_shadow_reports,
         serve_guest_elf_endpoint
     ),
     components(schemas(
@@ -47,16 +84,84 @@ use crate::utils::*;
         ComplianceOutcome,
         ProofMetadata,
         UserResponse,
-        PoolId
-    ))
+        PoolId,
+        Amount,
+        PoolExposure,
+        PoolPolicy,
+        Action,
+        ActionRules,
+        ActionRuleSets,
+        VolumeWindow,
+        WindowAllowance,
+        ShadowReport,
+        Divergence,
+        KycTier,
+        RiskBand,
+        TradingWindow,
+        Eligibility,
+        Weekday,
+        TimeOfDay,
+        Date,
+        PolicyDocument,
+        JurisdictionGroup,
+        AddressOverride,
+        OverrideEntry,
+        Acquisition,
+        PoolEntry,
+        PoolInfo,
+        Violation,
+        InvestorType,
+        InvestorCategory,
+        Rule,
+        RuleCheck,
+        TraceValue,
+        ExplainResponse,
+        RemediationStep,
+        Remediation,
+        PoolEligibility,
+        RemediationResponse
+    )),
+    modifiers(&AdminTokenScheme)
 )]
 struct ApiDoc;
 
+struct AdminTokenScheme;
+
+impl Modify for AdminTokenScheme {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        if let Some(components) = openapi.components.as_mut() {
+            components.add_security_scheme(
+                "admin_token",
+                SecurityScheme::Http(Http::new(HttpAuthScheme::Bearer)),
+            );
+        }
+    }
+}
+
 #[tokio::main]
 async fn main() -> Result<()> {
     // Load .env file
     dotenvy::dotenv().ok();
 
+    // `host lint [path]` checks a policy set without starting the server.
+    let mut args = env::args().skip(1);
+    match args.next().as_deref() {
+        None => {}
+        Some("lint") => {
+            let path = args
+                .next()
+                .or_else(|| env::var("POLICIES_PATH").ok())
+                .unwrap_or_else(|| DEFAULT_POLICIES_PATH.to_string());
+            PoolRegistry::load(&path, &load_holiday_calendars()?)?;
+            println!("✅ {} passes the policy linter", path);
+            return Ok(());
+        }
+        Some(command) => bail!(
+            "unknown command {}; usage: host [lint [POLICIES_PATH]]",
+            command
+        ),
+    }
+
     // --- Env vars ---
     let rpc_url = Url::parse(&env::var("RPC_URL")?)?;
     let private_key_hex = env::var("PRIVATE_KEY")?;
@@ -77,11 +182,62 @@ async fn main() -> Result<()> {
         .unwrap_or_else(|_| "https://mantle-usd.onrender.com/guest_elf".to_string());
     let guest_program_url = Url::parse(&guest_program_url)?;
 
+    let policies_path =
+        env::var("POLICIES_PATH").unwrap_or_else(|_| DEFAULT_POLICIES_PATH.to_string());
+    let registry = PoolRegistry::load(&policies_path, &load_holiday_calendars()?)?;
+
+    let volume_store_path =
+        env::var("VOLUME_STORE_PATH").unwrap_or_else(|_| DEFAULT_VOLUME_STORE_PATH.to_string());
+    let retention_secs = registry
+        .policies()
+        .all()
+        .iter()
+        .flat_map(|policy| &policy.volume_windows)
+        .map(|window| window.period_secs())
+        .max()
+        .unwrap_or(0);
+    let volumes = VolumeStore::load(&volume_store_path, retention_secs)?;
+
+    let acquisitions_path =
+        env::var("ACQUISITIONS_PATH").unwrap_or_else(|_| DEFAULT_ACQUISITIONS_PATH.to_string());
+    let acquisitions = AcquisitionStore::load(&acquisitions_path)?;
+
+    let investors_path =
+        env::var("INVESTORS_PATH").unwrap_or_else(|_| DEFAULT_INVESTORS_PATH.to_string());
+    let investors = InvestorStore::load(&investors_path)?;
+
+    let positions_path =
+        env::var("POSITIONS_PATH").unwrap_or_else(|_| DEFAULT_POSITIONS_PATH.to_string());
+    let positions = PositionStore::load(&positions_path)?;
+
+    let overrides_path =
+        env::var("OVERRIDES_PATH").unwrap_or_else(|_| DEFAULT_OVERRIDES_PATH.to_string());
+    let overrides = OverrideStore::load(&overrides_path)?;
+
+    let shadow_path = env::var("SHADOW_PATH").unwrap_or_else(|_| DEFAULT_SHADOW_PATH.to_string());
+    let shadow = ShadowStore::load(&shadow_path)?;
+    shadow.spawn_flusher(SHADOW_FLUSH_INTERVAL);
+
+    let admin_token = env::var("ADMIN_TOKEN")
+        .ok()
+        .filter(|token| !token.trim().is_empty());
+    if admin_token.is_none() {
+        println!("⚠️ ADMIN_TOKEN not set; /admin routes are disabled");
+    }
+
     // --- Axum server ---
     let state = Arc::new(AppState {
         signer,
         rpc_url,
         guest_program_url,
+        registry,
+        volumes,
+        acquisitions,
+        investors,
+        positions,
+        overrides,
+        shadow,
+        admin_token,
     });
 
     let cors = CorsLayer::new()
@@ -97,6 +253,21 @@ async fn main() -> Result<()> {
             get(get_validate_user).post(post_validate_user_handler),
         )
         .route("/compliance/pools", post(post_compliance_pools_handler))
+        .route("/compliance/explain", post(post_compliance_explain))
+        .route("/compliance/remediate", post(post_compliance_remediate))
+        .route("/pools", get(get_pools))
+        .route("/pools/{pool_id}", get(get_pool))
+        .route("/pools/{pool_id}/policies", get(get_pool_policies))
+        .route("/policies", get(get_policies))
+        .route("/policies/{policy_id}", get(get_policy))
+        .route("/jurisdictions", get(get_jurisdictions))
+        .route(
+            "/admin/overrides",
+            get(admin::get_overrides).put(admin::put_override),
+        )
+        .route("/admin/overrides/{address}", delete(admin::delete_override))
+        .route("/admin/acquisitions", post(admin::post_acquisition))
+        .route("/admin/shadow", get(admin::get_shadow_reports))
         .route("/guest_elf", get(serve_guest_elf_endpoint))
         .with_state(state.clone())
         .layer(cors);
@@ -138,14 +309,19 @@ async fn get_validate_user() -> Json<serde_json::Value> {
         "message": "POST /validate_user or /compliance/pools with full compliance payload",
         "schema": {
             "user": "0x...",
-            "pool_id": "gold | money_market | real_estate",
+            "pool_id": "registry key (see GET /pools) or RWAPool address",
+            "action": "optional: buy (default), sell, add_liquidity or remove_liquidity",
             "residency": "ISO country code",
             "kyc_level": "u8 >= 0",
             "aml_passed": "bool",
             "accredited_investor": "bool",
-            "exposure_musd": "current exposure in mUSD",
-            "requested_amount": "trade amount in mUSD",
-            "risk_score": "0-10"
+            "investor_type": "optional: individual or entity",
+            "investor_categories": "optional list of accredited, qualified_purchaser, professional_client, institutional",
+            "exposure_musd": "current exposure as a string of mUSD base units, or e.g. \"20000 mUSD\"",
+            "requested_amount": "swap amountIn as a string of mUSD base units, or e.g. \"10000 mUSD\"",
+            "risk_score": "0-10",
+            "allow_partial": "optional bool; approve the part of requested_amount that fits the caps",
+            "portfolio": "optional [{ pool_id, exposure_musd }] for the user's other pools; checked against the portfolio cap"
         }
     }))
 }
@@ -166,13 +342,7 @@ async fn post_validate_user_handler(
     State(state): State<Arc<AppState>>,
     Json(payload): Json<ComplianceRequest>,
 ) -> impl IntoResponse {
-    post_validate_user(
-        Json(payload),
-        &state.signer,
-        state.rpc_url.clone(),
-        state.guest_program_url.clone(),
-    )
-    .await
+    post_validate_user(Json(payload), &state).await
 }
 
 #[utoipa::path(
@@ -190,13 +360,196 @@ async fn post_compliance_pools_handler(
     State(state): State<Arc<AppState>>,
     Json(payload): Json<ComplianceRequest>,
 ) -> impl IntoResponse {
-    post_validate_user(
-        Json(payload),
-        &state.signer,
-        state.rpc_url.clone(),
-        state.guest_program_url.clone(),
+    post_validate_user(Json(payload), &state).await
+}
+
+// POST /compliance/explain
+#[utoipa::path(
+    post,
+    path = "/compliance/explain",
+    tag = "Compliance",
+    request_body = ComplianceRequest,
+    responses(
+        (status = 200, description = "Per-rule evaluation trace (no proof is generated)", body = ExplainResponse),
+        (status = 400, description = "Invalid payload"),
+        (status = 404, description = "Unknown pool")
+    )
+)]
+async fn post_compliance_explain(
+    State(state): State<Arc<AppState>>,
+    Json(mut payload): Json<ComplianceRequest>,
+) -> Result<Json<ExplainResponse>, StatusCode> {
+    let now = unix_now();
+    let (pool, policy) = state
+        .registry
+        .resolve_request(&mut payload, now)
+        .ok_or(StatusCode::NOT_FOUND)?;
+
+    let context = state.context(&payload.user, &pool.pool_id, policy, now);
+    let (outcome, trace) = evaluate_with_trace(&payload, policy, &context);
+    Ok(Json(ExplainResponse { outcome, trace }))
+}
+
+// POST /compliance/remediate
+#[utoipa::path(
+    post,
+    path = "/compliance/remediate",
+    tag = "Compliance",
+    request_body = ComplianceRequest,
+    responses(
+        (status = 200, description = "Changes needed to pass, and eligibility for the other pools (no proof is generated)", body = RemediationResponse),
+        (status = 400, description = "Invalid payload"),
+        (status = 404, description = "Unknown pool")
+    )
+)]
+async fn post_compliance_remediate(
+    State(state): State<Arc<AppState>>,
+    Json(payload): Json<ComplianceRequest>,
+) -> Result<Json<RemediationResponse>, StatusCode> {
+    remediation_report(&state, &payload)
+        .map(Json)
+        .ok_or(StatusCode::NOT_FOUND)
+}
+
+// GET /pools
+#[utoipa::path(
+    get,
+    path = "/pools",
+    tag = "Pools",
+    responses(
+        (status = 200, description = "Pools registered with the API", body = [PoolInfo])
+    )
+)]
+async fn get_pools(State(state): State<Arc<AppState>>) -> Json<Vec<PoolInfo>> {
+    let now = unix_now();
+    Json(
+        state
+            .registry
+            .pools()
+            .iter()
+            .filter_map(|pool| state.registry.resolve(&pool.pool_id, now))
+            .map(|(pool, policy)| PoolInfo::new(pool, policy, state.investors.count(&pool.pool_id)))
+            .collect(),
+    )
+}
+
+// GET /pools/{pool_id}
+#[utoipa::path(
+    get,
+    path = "/pools/{pool_id}",
+    tag = "Pools",
+    params(("pool_id" = String, Path, description = "Registry key or RWAPool address")),
+    responses(
+        (status = 200, description = "Registered pool", body = PoolInfo),
+        (status = 404, description = "Unknown pool")
+    )
+)]
+async fn get_pool(
+    State(state): State<Arc<AppState>>,
+    Path(pool_id): Path<PoolId>,
+) -> Result<Json<PoolInfo>, StatusCode> {
+    state
+        .registry
+        .resolve(&pool_id, unix_now())
+        .map(|(pool, policy)| {
+            Json(PoolInfo::new(
+                pool,
+                policy,
+                state.investors.count(&pool.pool_id),
+            ))
+        })
+        .ok_or(StatusCode::NOT_FOUND)
+}
+
+// GET /pools/{pool_id}/policies
+#[utoipa::path(
+    get,
+    path = "/pools/{pool_id}/policies",
+    tag = "Pools",
+    params(("pool_id" = String, Path, description = "Registry key or RWAPool address")),
+    responses(
+        (status = 200, description = "Every version of the pool's policy, oldest first, including scheduled ones", body = [PolicyDocument]),
+        (status = 404, description = "Unknown pool")
+    )
+)]
+async fn get_pool_policies(
+    State(state): State<Arc<AppState>>,
+    Path(pool_id): Path<PoolId>,
+) -> Result<Json<Vec<PolicyDocument>>, StatusCode> {
+    let pool = state.registry.pool(&pool_id).ok_or(StatusCode::NOT_FOUND)?;
+    Ok(Json(
+        state
+            .registry
+            .policies()
+            .history(&pool.policy)
+            .into_iter()
+            .map(PolicyDocument::from)
+            .collect(),
+    ))
+}
+
+// GET /policies
+#[utoipa::path(
+    get,
+    path = "/policies",
+    tag = "Policies",
+    responses(
+        (status = 200, description = "Pool policies currently enforced", body = [PolicyDocument])
+    )
+)]
+async fn get_policies(State(state): State<Arc<AppState>>) -> Json<Vec<PolicyDocument>> {
+    Json(
+        state
+            .registry
+            .policies()
+            .active(unix_now())
+            .map(PolicyDocument::from)
+            .collect(),
+    )
+}
+
+// GET /policies/{policy_id}
+#[utoipa::path(
+    get,
+    path = "/policies/{policy_id}",
+    tag = "Policies",
+    params(("policy_id" = String, Path, description = "Policy identifier")),
+    responses(
+        (status = 200, description = "Policy document", body = PolicyDocument),
+        (status = 404, description = "Unknown policy")
+    )
+)]
+async fn get_policy(
+    State(state): State<Arc<AppState>>,
+    Path(policy_id): Path<String>,
+) -> Result<Json<PolicyDocument>, StatusCode> {
+    state
+        .registry
+        .policies()
+        .get(&policy_id, unix_now())
+        .map(|policy| Json(PolicyDocument::from(policy)))
+        .ok_or(StatusCode::NOT_FOUND)
+}
+
+// GET /jurisdictions
+#[utoipa::path(
+    get,
+    path = "/jurisdictions",
+    tag = "Policies",
+    responses(
+        (status = 200, description = "Jurisdiction groups residency lists may reference", body = [JurisdictionGroup])
+    )
+)]
+async fn get_jurisdictions() -> Json<Vec<JurisdictionGroup>> {
+    Json(
+        compliance_core::jurisdiction::GROUPS
+            .iter()
+            .map(|(name, members)| JurisdictionGroup {
+                name: name.to_string(),
+                members: members.iter().map(|code| code.to_string()).collect(),
+            })
+            .collect(),
     )
-    .await
 }
 
 #[utoipa::path(
@@ -212,18 +565,46 @@ async fn serve_guest_elf_endpoint() -> impl IntoResponse {
 }
 
 async fn post_validate_user(
-    Json(payload): Json<ComplianceRequest>,
-    signer: &PrivateKeySigner,
-    rpc_url: Url,
-    guest_program_url: Url,
+    Json(mut payload): Json<ComplianceRequest>,
+    state: &AppState,
 ) -> Json<UserResponse> {
+    let now = unix_now();
+    let Some((pool, policy)) = state.registry.resolve_request(&mut payload, now) else {
+        let reason = format!("Pool {} is not registered", payload.pool_id);
+        return Json(UserResponse {
+            message: reason.clone(),
+            outcome: denied_outcome(&payload, reason, None),
+            proof: None,
+        });
+    };
+
+    let input = ComplianceInput {
+        request: payload.clone(),
+        policy: policy.clone(),
+        context: state.context(&payload.user, &pool.pool_id, policy, now),
+    };
+
+    let preliminary_outcome = evaluate(&input.request, &input.policy, &input.context);
+
     // Check cache first
-    if let Some(cached_response) = get_cached_response(&payload) {
+    if let Some(cached_response) = get_cached_response(&input) {
         println!("🎯 Cache hit! Returning cached response");
         return Json(cached_response);
     }
 
-    let preliminary_outcome = evaluate(&payload);
+    // The candidate policy sees the same fresh decisions, but its outcome is only recorded,
+    // never returned. Retries of the same input are counted once.
+    if let Some(candidate) = state.registry.shadow_policy(pool, now) {
+        let context = state.context(&payload.user, &pool.pool_id, candidate, now);
+        let shadow_outcome = evaluate(&input.request, candidate, &context);
+        state.shadow.record(
+            &get_cache_key(&input),
+            candidate,
+            &preliminary_outcome,
+            &shadow_outcome,
+        );
+    }
+
     if !preliminary_outcome.allowed {
         return Json(UserResponse {
             message: preliminary_outcome.reason.clone(),
@@ -232,32 +613,125 @@ async fn post_validate_user(
         });
     }
 
+    // Take the volume and the investor slot this inflow uses before proving, so concurrent
+    // requests cannot be approved against the same room; both are given back if the proof
+    // does not go through.
+    let inflow = payload.action.increases_exposure();
+    let rules = policy.rules_for(payload.action);
+    let reserved_volume = if inflow {
+        let amount = preliminary_outcome.approved_amount;
+        let windows = if rules.applies(Rule::VolumeWindow) {
+            policy.volume_windows.as_slice()
+        } else {
+            &[]
+        };
+        match state
+            .volumes
+            .reserve(&payload.user, &pool.pool_id, amount, windows, now)
+        {
+            Ok(true) => Some(amount),
+            Ok(false) => return concurrent_denial(state, &payload, pool, policy, now),
+            Err(e) => {
+                eprintln!("❌ Failed to record approved volume: {:?}", e);
+                Some(amount)
+            }
+        }
+    } else {
+        None
+    };
+    let release_volume = || {
+        if let Some(amount) = reserved_volume {
+            if let Err(e) = state
+                .volumes
+                .release(&payload.user, &pool.pool_id, amount, now)
+            {
+                eprintln!("❌ Failed to release reserved volume: {:?}", e);
+            }
+        }
+    };
+    let new_holder = if inflow {
+        let max_investors = policy
+            .max_investors
+            .filter(|_| rules.applies(Rule::InvestorCap));
+        match state
+            .investors
+            .admit(&payload.user, &pool.pool_id, max_investors)
+        {
+            Ok(Some(new_holder)) => new_holder,
+            Ok(None) => {
+                release_volume();
+                return concurrent_denial(state, &payload, pool, policy, now);
+            }
+            Err(e) => {
+                eprintln!("❌ Failed to record pool holders: {:?}", e);
+                true
+            }
+        }
+    } else {
+        false
+    };
+    let release_reservations = || {
+        release_volume();
+        if new_holder {
+            if let Err(e) = state.investors.release(&payload.user, &pool.pool_id) {
+                eprintln!("❌ Failed to release investor slot: {:?}", e);
+            }
+        }
+    };
+
     let response = match submit_proof_request(
-        &signer,
-        rpc_url,
-        guest_program_url,
-        Json(payload.clone()),
+        &state.signer,
+        state.rpc_url.clone(),
+        state.guest_program_url.clone(),
+        Json(input.clone()),
     )
     .await
     {
         Ok(resp) => {
+            let outcome = &resp.outcome;
+            if !outcome.allowed {
+                release_reservations();
+            }
+            // A proven buy or deposit starts the lock-up even before the indexer reports it.
+            // Lots are not tracked, so each one restarts the lock-up for the whole position.
+            if outcome.allowed && outcome.action.increases_exposure() {
+                let acquisition = Acquisition {
+                    user: outcome.user.clone(),
+                    pool_id: outcome.pool_id.clone(),
+                    acquired_at: input.context.now,
+                    tx_hash: None,
+                };
+                if let Err(e) = state.acquisitions.record(acquisition, unix_now()) {
+                    eprintln!("❌ Failed to record acquisition: {:?}", e);
+                }
+            }
+            // An approved exit of the whole proven position frees the holder's slot.
+            if outcome.allowed {
+                match state.positions.apply(
+                    &outcome.user,
+                    &outcome.pool_id,
+                    outcome.action,
+                    outcome.approved_amount,
+                ) {
+                    Ok(left) if left.is_zero() && !outcome.action.increases_exposure() => {
+                        if let Err(e) = state.investors.release(&outcome.user, &outcome.pool_id) {
+                            eprintln!("❌ Failed to record pool holders: {:?}", e);
+                        }
+                    }
+                    Ok(_) => {}
+                    Err(e) => eprintln!("❌ Failed to record position: {:?}", e),
+                }
+            }
             // Cache the successful response
-            cache_response(&payload, &resp);
+            cache_response(&input, &resp);
             resp
         }
         Err(e) => {
             eprintln!("❌ Proof submission failed: {:?}", e);
+            release_reservations();
             return Json(UserResponse {
                 message: format!("Proof generation failed: {}", e),
-                outcome: ComplianceOutcome {
-                    user: payload.user.clone(),
-                    pool_id: payload.pool_id,
-                    allowed: false,
-                    reason: format!("System error: {}", e),
-                    max_allocation: 0,
-                    requested_amount: payload.requested_amount,
-                    exposure_musd: payload.exposure_musd,
-                },
+                outcome: denied_outcome(&payload, format!("System error: {}", e), Some(policy)),
                 proof: None,
             });
         }
@@ -265,3 +739,60 @@ async fn post_validate_user(
 
     response
 }
+
+/// The denial for a request whose reservation failed because a concurrent request took the
+/// room first: the policy's own denial under the updated context, or a retry hint if the
+/// request would now pass at a different amount.
+fn concurrent_denial(
+    state: &AppState,
+    payload: &ComplianceRequest,
+    pool: &PoolEntry,
+    policy: &PoolPolicy,
+    now: u64,
+) -> Json<UserResponse> {
+    let context = state.context(&payload.user, &pool.pool_id, policy, now);
+    let mut outcome = evaluate(payload, policy, &context);
+    if outcome.allowed {
+        let reason = "Capacity was taken by a concurrent request; retry".to_string();
+        outcome = denied_outcome(payload, reason, Some(policy));
+    }
+    Json(UserResponse {
+        message: outcome.reason.clone(),
+        outcome,
+        proof: None,
+    })
+}
+
+/// A denial decided by the host rather than the policy; `policy` is `None` when the pool has
+/// no policy in force, leaving the version and hash zeroed.
+fn denied_outcome(
+    payload: &ComplianceRequest,
+    reason: String,
+    policy: Option<&PoolPolicy>,
+) -> ComplianceOutcome {
+    ComplianceOutcome {
+        user: payload.user.clone(),
+        pool_id: payload.pool_id.clone(),
+        action: payload.action,
+        tier: None,
+        address_override: None,
+        allowed: false,
+        reason,
+        violations: Vec::new(),
+        max_allocation: Amount::ZERO,
+        requested_amount: payload.requested_amount,
+        approved_amount: Amount::ZERO,
+        exposure_musd: payload.exposure_musd,
+        portfolio_exposure_musd: payload.portfolio_exposure(),
+        window_allowances: Vec::new(),
+        unlocks_at: None,
+        evaluated_at: unix_now(),
+        policy_version: policy.map_or(0, |policy| policy.version),
+        policy_hash: policy.map(PoolPolicy::policy_hash).unwrap_or_default(),
+    }
+}
+
+fn load_holiday_calendars() -> Result<HolidayCalendars> {
+    let path = env::var("HOLIDAYS_PATH").unwrap_or_else(|_| DEFAULT_HOLIDAYS_PATH.to_string());
+    HolidayCalendars::load(path)
+}
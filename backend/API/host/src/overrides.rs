@@ -0,0 +1,126 @@
+use anyhow::{ensure, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use utoipa::ToSchema;
+
+use crate::registry::is_address;
+use crate::types::{AddressOverride, PoolId};
+use crate::utils::write_atomically;
+
+pub const DEFAULT_OVERRIDES_PATH: &str = "data/overrides.json";
+
+/// An operator decision for one wallet, scoped to one pool or, without `pool_id`, to all.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct OverrideEntry {
+    #[schema(example = "0x2111222233334444555566667777888899990000")]
+    pub address: String,
+    /// Registry key of the pool; omit for a global override.
+    #[serde(default)]
+    #[schema(value_type = Option<String>, example = "gold")]
+    pub pool_id: Option<PoolId>,
+    pub kind: AddressOverride,
+    /// Why the override exists, e.g. a court order or market-maker agreement reference.
+    #[schema(example = "Court order 2026-CV-0142")]
+    pub reason: String,
+    /// Unix seconds; set by the server.
+    #[serde(default)]
+    pub created_at: u64,
+}
+
+/// Per-pool and global address overrides, persisted as a JSON file.
+#[derive(Debug, Clone)]
+pub struct OverrideStore {
+    path: PathBuf,
+    entries: Arc<Mutex<Vec<OverrideEntry>>>,
+}
+
+impl OverrideStore {
+    /// Opens the store at `path`, starting empty if the file does not exist yet.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let entries: Vec<OverrideEntry> = if path.exists() {
+            let raw = fs::read_to_string(&path)
+                .with_context(|| format!("failed to read overrides {}", path.display()))?;
+            serde_json::from_str(&raw)
+                .with_context(|| format!("invalid overrides {}", path.display()))?
+        } else {
+            Vec::new()
+        };
+        println!(
+            "✅ Loaded {} address overrides from {}",
+            entries.len(),
+            path.display()
+        );
+
+        Ok(Self {
+            path,
+            entries: Arc::new(Mutex::new(entries)),
+        })
+    }
+
+    /// The override for `address` in `pool_id`. A deny, pool-specific or global, always wins
+    /// over an allow so a blocked wallet cannot be let back in by a narrower entry.
+    pub fn resolve(&self, address: &str, pool_id: &PoolId) -> Option<AddressOverride> {
+        let entries = self.entries.lock().unwrap();
+        let matching = entries.iter().filter(|entry| {
+            entry.address.eq_ignore_ascii_case(address)
+                && entry.pool_id.as_ref().is_none_or(|pool| pool == pool_id)
+        });
+        let mut resolved = None;
+        for entry in matching {
+            if entry.kind == AddressOverride::Deny {
+                return Some(AddressOverride::Deny);
+            }
+            resolved = Some(entry.kind);
+        }
+        resolved
+    }
+
+    pub fn all(&self) -> Vec<OverrideEntry> {
+        self.entries.lock().unwrap().clone()
+    }
+
+    /// Adds `entry`, replacing any override for the same address and scope.
+    pub fn upsert(&self, mut entry: OverrideEntry, now: u64) -> Result<OverrideEntry> {
+        entry.address = entry.address.trim().to_ascii_lowercase();
+        ensure!(
+            is_address(&entry.address),
+            "{} is not a valid address",
+            entry.address
+        );
+        ensure!(
+            !entry.reason.trim().is_empty(),
+            "an override needs a reason"
+        );
+        entry.created_at = now;
+
+        let mut entries = self.entries.lock().unwrap();
+        entries.retain(|existing| {
+            !(existing.address == entry.address && existing.pool_id == entry.pool_id)
+        });
+        entries.push(entry.clone());
+        self.persist(&entries)?;
+        Ok(entry)
+    }
+
+    /// Removes the override for `address` in the given scope; returns whether one existed.
+    pub fn remove(&self, address: &str, pool_id: Option<&PoolId>) -> Result<bool> {
+        let mut entries = self.entries.lock().unwrap();
+        let before = entries.len();
+        entries.retain(|entry| {
+            !(entry.address.eq_ignore_ascii_case(address) && entry.pool_id.as_ref() == pool_id)
+        });
+        let removed = entries.len() != before;
+        if removed {
+            self.persist(&entries)?;
+        }
+        Ok(removed)
+    }
+
+    fn persist(&self, entries: &[OverrideEntry]) -> Result<()> {
+        write_atomically(&self.path, &serde_json::to_vec_pretty(entries)?)
+    }
+}
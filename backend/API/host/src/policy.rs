@@ -1,153 +1,438 @@
-use crate::types::{ComplianceOutcome, ComplianceRequest, PoolId};
-
-struct PoolPolicy {
-    name: &'static str,
-    allowed_residencies: &'static [&'static str],
-    banned_residencies: &'static [&'static str],
-    max_single_trade: u64,
-    max_total_exposure: u64,
-    min_kyc_level: u8,
-    max_risk_score: u8,
-    require_accreditation: bool,
-    pool_id: PoolId,
-}
+use anyhow::{bail, ensure, Context, Result};
+use compliance_core::jurisdiction;
+use std::collections::HashSet;
 
-const GOLD_ALLOWED: &[&str] = &["US", "CA", "UK", "DE", "FR", "SG", "AE"];
-const MONEY_MARKET_ALLOWED: &[&str] = &["US", "CA", "UK", "DE", "FR"];
-const REAL_ESTATE_BANNED: &[&str] = &["RU", "KP", "IR", "SY"];
+use crate::holidays::HolidayCalendars;
+use crate::lint::{lint, Finding, Severity};
+use crate::types::{Action, Amount, PoolPolicy, RiskBand, Rule};
 
-pub fn evaluate(request: &ComplianceRequest) -> ComplianceOutcome {
-    let policy = policy_for(request.pool_id);
-    let normalized_residency = request.residency.to_ascii_uppercase();
+pub use compliance_core::{evaluate, evaluate_with_trace};
 
-    let mut failures: Vec<String> = Vec::new();
+/// Validated pool policies, keyed by `policy_id` and `version`.
+///
+/// Every version of a policy is kept, so decisions can be traced back to the version they
+/// were evaluated under; the one in force at a given time is the latest to have taken effect.
+#[derive(Debug, Clone)]
+pub struct PolicySet {
+    policies: Vec<PoolPolicy>,
+}
 
-    if !request.aml_passed {
-        failures.push("AML screening failed".to_string());
-    }
+impl PolicySet {
+    /// Validates `policies`, merging the dates of the holiday calendars they name.
+    pub fn new(policies: Vec<PoolPolicy>, calendars: &HolidayCalendars) -> Result<Self> {
+        ensure!(!policies.is_empty(), "no pool policies configured");
 
-    if normalized_residency.is_empty() {
-        failures.push("Residency not provided".to_string());
-    } else {
-        if !policy.allowed_residencies.is_empty()
-            && !contains(policy.allowed_residencies, normalized_residency.as_str())
-        {
-            failures.push(format!(
-                "{} pool is not open to residency {}",
-                policy.name, normalized_residency
-            ));
+        let mut seen = HashSet::new();
+        let mut normalized = Vec::with_capacity(policies.len());
+        for policy in policies {
+            ensure!(
+                seen.insert((policy.policy_id.clone(), policy.version)),
+                "duplicate policy {} version {}",
+                policy.policy_id,
+                policy.version
+            );
+            normalized.push(validate(policy, calendars)?);
+        }
+
+        // Refuse the whole set on any error, listing every problem, so one run is enough to
+        // fix them all. Warnings are only printed.
+        let (errors, warnings): (Vec<Finding>, Vec<Finding>) = normalized
+            .iter()
+            .flat_map(lint)
+            .partition(|finding| finding.severity == Severity::Error);
+        for warning in &warnings {
+            println!("⚠️ {}", warning);
+        }
+        if !errors.is_empty() {
+            let messages: Vec<String> = errors.iter().map(ToString::to_string).collect();
+            bail!(
+                "policy set has {} problem(s):\n  - {}",
+                messages.len(),
+                messages.join("\n  - ")
+            );
         }
 
-        if !policy.banned_residencies.is_empty()
-            && contains(policy.banned_residencies, normalized_residency.as_str())
-        {
-            failures.push(format!(
-                "Residency {} is blocked for {} pool",
-                normalized_residency, policy.name
-            ));
+        let set = Self {
+            policies: normalized,
+        };
+        // `get` picks the latest version to have taken effect, so later versions must take
+        // effect later or they would shadow, or be shadowed by, their predecessors.
+        for policy in &set.policies {
+            ensure!(
+                set.history(&policy.policy_id)
+                    .windows(2)
+                    .all(|pair| pair[0].effective_from < pair[1].effective_from),
+                "versions of policy {} must take effect in version order, each strictly after \
+                 the previous one",
+                policy.policy_id
+            );
         }
+        Ok(set)
     }
 
-    if request.kyc_level < policy.min_kyc_level {
-        failures.push(format!(
-            "{} pool requires KYC level {} or higher (provided {})",
-            policy.name, policy.min_kyc_level, request.kyc_level
-        ));
+    /// The version of `policy_id` in force at `now` (Unix seconds), if one has taken effect.
+    pub fn get(&self, policy_id: &str, now: u64) -> Option<&PoolPolicy> {
+        self.history(policy_id)
+            .into_iter()
+            .rev()
+            .find(|policy| policy.effective_from <= now)
     }
 
-    if request.risk_score > policy.max_risk_score {
-        failures.push(format!(
-            "Risk score {} exceeds {} pool limit {}",
-            request.risk_score, policy.name, policy.max_risk_score
-        ));
+    /// Every version of `policy_id`, oldest first, including ones not yet in force.
+    pub fn history(&self, policy_id: &str) -> Vec<&PoolPolicy> {
+        let mut versions: Vec<&PoolPolicy> = self
+            .policies
+            .iter()
+            .filter(|policy| policy.policy_id == policy_id)
+            .collect();
+        versions.sort_by_key(|policy| policy.version);
+        versions
     }
 
-    if policy.require_accreditation && !request.accredited_investor {
-        failures.push(format!(
-            "{} pool is limited to accredited investors",
-            policy.name
-        ));
+    /// The version of each policy in force at `now`, skipping policies none of whose versions
+    /// has taken effect yet.
+    pub fn active(&self, now: u64) -> impl Iterator<Item = &PoolPolicy> {
+        self.policies.iter().filter(move |policy| {
+            self.get(&policy.policy_id, now)
+                .is_some_and(|active| std::ptr::eq(active, *policy))
+        })
     }
 
-    if request.requested_amount > policy.max_single_trade {
-        failures.push(format!(
-            "Requested amount {} mUSD exceeds {} single-trade limit of {} mUSD",
-            request.requested_amount, policy.name, policy.max_single_trade
-        ));
+    /// Every version of every policy.
+    pub fn all(&self) -> &[PoolPolicy] {
+        &self.policies
     }
+}
 
-    let projected_exposure = request
-        .exposure_musd
-        .saturating_add(request.requested_amount);
-    if projected_exposure > policy.max_total_exposure {
-        failures.push(format!(
-            "Projected exposure {} mUSD exceeds {} pool cap of {} mUSD",
-            projected_exposure, policy.name, policy.max_total_exposure
-        ));
+fn validate(mut policy: PoolPolicy, calendars: &HolidayCalendars) -> Result<PoolPolicy> {
+    ensure!(
+        !policy.policy_id.trim().is_empty(),
+        "policy has an empty policy_id"
+    );
+    ensure!(
+        policy.version > 0,
+        "policy {} has version 0; versions start at 1",
+        policy.policy_id
+    );
+    let id = format!("{} version {}", policy.policy_id, policy.version);
+    ensure!(
+        !policy.name.trim().is_empty(),
+        "policy {} has an empty name",
+        id
+    );
+    ensure!(
+        !policy.max_single_trade.is_zero(),
+        "policy {} must allow a non-zero single trade",
+        id
+    );
+    ensure!(
+        !policy.max_total_exposure.is_zero(),
+        "policy {} must allow a non-zero total exposure",
+        id
+    );
+    ensure!(
+        policy.max_portfolio_exposure != Some(Amount::ZERO),
+        "policy {} sets a zero portfolio cap",
+        id
+    );
+    ensure!(
+        policy.lockup_days != Some(0),
+        "policy {} sets a zero-day lock-up; omit lockup_days instead",
+        id
+    );
+    for (field, value) in [
+        ("min_trade", policy.min_trade),
+        ("lot_size", policy.lot_size),
+        ("min_initial_investment", policy.min_initial_investment),
+    ] {
+        ensure!(
+            value != Some(Amount::ZERO),
+            "policy {} sets a zero {}; omit it instead",
+            id,
+            field
+        );
     }
+    ensure!(
+        policy.max_investors != Some(0),
+        "policy {} sets a zero investor cap",
+        id
+    );
 
-    let allowed = failures.is_empty();
-    let max_allocation = policy
-        .max_total_exposure
-        .saturating_sub(request.exposure_musd);
-    let reason = if allowed {
-        format!(
-            "{} pool approval: user may allocate up to {} mUSD more (requested {}).",
-            policy.name, max_allocation, request.requested_amount
-        )
-    } else {
-        failures.join(" | ")
-    };
-
-    ComplianceOutcome {
-        user: request.user.clone(),
-        pool_id: policy.pool_id,
-        allowed,
-        reason,
-        max_allocation,
-        requested_amount: request.requested_amount,
-        exposure_musd: request.exposure_musd,
+    // Groups are expanded to country codes, and the lists sorted, so the policy hash commits
+    // to the exact set of codes regardless of how the file spells it.
+    for list in [
+        &mut policy.allowed_residencies,
+        &mut policy.banned_residencies,
+    ] {
+        *list = jurisdiction::expand(list).with_context(|| format!("policy {}", id))?;
     }
-}
+    policy.allowed_investor_types.sort();
+    policy.allowed_investor_types.dedup();
+    policy.required_investor_categories.sort();
+    policy.required_investor_categories.dedup();
+
+    for window in &policy.volume_windows {
+        ensure!(
+            window.period_days > 0 && !window.max_volume.is_zero(),
+            "policy {} has an empty volume window",
+            id
+        );
+    }
+    policy
+        .volume_windows
+        .sort_by_key(|window| window.period_days);
+    ensure!(
+        policy
+            .volume_windows
+            .windows(2)
+            .all(|pair| pair[0].period_days != pair[1].period_days),
+        "policy {} declares two volume windows with the same period",
+        id
+    );
+
+    for window in &mut policy.trading_windows {
+        ensure!(
+            window.opens < window.closes,
+            "policy {} has a trading window from {} to {}; windows cannot be empty or cross \
+             midnight, so split it in two",
+            id,
+            window.opens,
+            window.closes
+        );
+        window.weekdays.sort();
+        window.weekdays.dedup();
+    }
+    if let Some(name) = &policy.holiday_calendar {
+        let dates = calendars.get(name).with_context(|| {
+            format!("policy {} references unknown holiday calendar {}", id, name)
+        })?;
+        policy.holidays.extend_from_slice(dates);
+    }
+    policy.holidays.sort();
+    policy.holidays.dedup();
+
+    let mut tier_names = HashSet::new();
+    for tier in &policy.tiers {
+        ensure!(
+            !tier.name.trim().is_empty(),
+            "policy {} has a tier with an empty name",
+            id
+        );
+        ensure!(
+            tier_names.insert(tier.name.clone()),
+            "policy {} declares tier {} twice",
+            id,
+            tier.name
+        );
+        ensure!(
+            !tier.max_single_trade.is_zero()
+                && !tier.max_total_exposure.is_zero()
+                && tier.max_portfolio_exposure != Some(Amount::ZERO),
+            "policy {} sets a zero cap for tier {}",
+            id,
+            tier.name
+        );
+    }
+    // `tier_for` picks the last tier a user reaches, so tiers must run from lowest to highest.
+    policy
+        .tiers
+        .sort_by_key(|tier| (tier.min_kyc_level, tier.require_accreditation));
+
+    for band in &policy.risk_bands {
+        ensure!(
+            band.allocation_bps <= RiskBand::FULL_BPS,
+            "policy {} grants more than the full cap to risk score {}",
+            id,
+            band.max_risk_score
+        );
+    }
+    // `risk_band_for` picks the first band covering a score, so bands must be ascending.
+    policy.risk_bands.sort_by_key(|band| band.max_risk_score);
+    ensure!(
+        policy
+            .risk_bands
+            .windows(2)
+            .all(|pair| pair[0].max_risk_score != pair[1].max_risk_score),
+        "policy {} declares two risk bands for the same score",
+        id
+    );
 
-fn policy_for(pool_id: PoolId) -> PoolPolicy {
-    match pool_id {
-        PoolId::Gold => PoolPolicy {
-            name: "Gold",
-            allowed_residencies: GOLD_ALLOWED,
-            banned_residencies: &[],
-            max_single_trade: 50_000,
-            max_total_exposure: 150_000,
-            min_kyc_level: 2,
-            max_risk_score: 4,
-            require_accreditation: false,
-            pool_id: PoolId::Gold,
-        },
-        PoolId::MoneyMarket => PoolPolicy {
-            name: "Money Market",
-            allowed_residencies: MONEY_MARKET_ALLOWED,
-            banned_residencies: &[],
-            max_single_trade: 25_000,
-            max_total_exposure: 50_000,
-            min_kyc_level: 3,
-            max_risk_score: 3,
-            require_accreditation: true,
-            pool_id: PoolId::MoneyMarket,
-        },
-        PoolId::RealEstate => PoolPolicy {
-            name: "Real Estate",
-            allowed_residencies: &[],
-            banned_residencies: REAL_ESTATE_BANNED,
-            max_single_trade: 200_000,
-            max_total_exposure: 500_000,
-            min_kyc_level: 2,
-            max_risk_score: 5,
-            require_accreditation: true,
-            pool_id: PoolId::RealEstate,
-        },
+    for action in Action::ALL {
+        let rules = policy.actions.get_mut(action);
+        ensure!(
+            rules.max_single_trade != Some(Amount::ZERO)
+                && rules.max_total_exposure != Some(Amount::ZERO),
+            "policy {} sets a zero cap for {:?}",
+            id,
+            action
+        );
+        ensure!(
+            !rules.applies(Rule::AddressOverride),
+            "policy {} lists address_override for {:?}; overrides apply to every action",
+            id,
+            action
+        );
+        // Rules run in evaluation order anyway; sorting keeps the hash independent of file order.
+        rules.rules.sort();
+        rules.rules.dedup();
     }
+
+    Ok(policy)
 }
 
-fn contains(list: &[&str], value: &str) -> bool {
-    list.iter().any(|entry| entry.eq_ignore_ascii_case(value))
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::holidays::DEFAULT_HOLIDAYS_PATH;
+    use crate::test_support::{request, POLICY};
+    use crate::types::{Date, EvaluationContext, Violation};
+    use serde::Deserialize;
+    use std::path::Path;
+
+    #[derive(Deserialize)]
+    struct PolicyFile {
+        policies: Vec<PoolPolicy>,
+    }
+
+    /// Loads `[[policies]]` entries, each being [`POLICY`] followed by `overrides`.
+    fn load(overrides: &[&str], calendars: &HolidayCalendars) -> Result<PolicySet> {
+        let raw: String = overrides
+            .iter()
+            .map(|extra| format!("[[policies]]\n{POLICY}\n{extra}\n"))
+            .collect();
+        let file: PolicyFile = toml::from_str(&raw)?;
+        PolicySet::new(file.policies, calendars)
+    }
+
+    fn repo_calendars() -> HolidayCalendars {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("..")
+            .join(DEFAULT_HOLIDAYS_PATH);
+        HolidayCalendars::load(path).unwrap()
+    }
+
+    #[test]
+    fn rejects_empty_and_overnight_trading_windows() {
+        for (opens, closes) in [("14:00", "09:00"), ("09:00", "09:00")] {
+            let window =
+                format!("trading_windows = [{{ opens = \"{opens}\", closes = \"{closes}\" }}]");
+            let error = load(&[&window], &HolidayCalendars::default()).unwrap_err();
+            assert!(
+                error
+                    .to_string()
+                    .contains("cannot be empty or cross midnight"),
+                "{error}"
+            );
+        }
+        let window = r#"trading_windows = [{ opens = "09:00", closes = "14:00" }]"#;
+        assert!(load(&[window], &HolidayCalendars::default()).is_ok());
+    }
+
+    #[test]
+    fn merges_holidays_from_the_repo_calendar() {
+        let policies = load(&[r#"holiday_calendar = "us_market""#], &repo_calendars()).unwrap();
+        let policy = policies.get("gold", 0).unwrap();
+        let christmas: Date = "2026-12-25".parse().unwrap();
+        assert!(policy.is_holiday(christmas));
+
+        let request = request();
+        let context = EvaluationContext {
+            now: christmas.start() + 12 * 3_600,
+            ..EvaluationContext::default()
+        };
+        let outcome = evaluate(&request, policy, &context);
+        assert_eq!(
+            outcome.violations,
+            vec![Violation::MarketHoliday { date: christmas }]
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_holiday_calendars() {
+        let error = load(&[r#"holiday_calendar = "lse""#], &repo_calendars()).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "policy gold version 1 references unknown holiday calendar lse"
+        );
+    }
+
+    #[test]
+    fn selects_the_latest_version_in_force() {
+        let policies = load(
+            &[
+                "",
+                "version = 2\neffective_from = 1000\nmax_investors = 99",
+                "version = 3\neffective_from = 2000\nlockup_days = 30",
+            ],
+            &HolidayCalendars::default(),
+        )
+        .unwrap();
+        let version_at = |now| policies.get("gold", now).map(|policy| policy.version);
+
+        assert_eq!(version_at(999), Some(1));
+        assert_eq!(version_at(1000), Some(2));
+        assert_eq!(version_at(1999), Some(2));
+        assert_eq!(version_at(2000), Some(3));
+        assert_eq!(version_at(u64::MAX), Some(3));
+        assert_eq!(policies.get("silver", 2000), None);
+
+        let history: Vec<u32> = policies
+            .history("gold")
+            .iter()
+            .map(|policy| policy.version)
+            .collect();
+        assert_eq!(history, [1, 2, 3]);
+        let active: Vec<u32> = policies.active(1500).map(|policy| policy.version).collect();
+        assert_eq!(active, [2]);
+    }
+
+    #[test]
+    fn no_version_is_in_force_before_the_first_takes_effect() {
+        let policies = load(&["effective_from = 1000"], &HolidayCalendars::default()).unwrap();
+        assert_eq!(policies.get("gold", 999), None);
+        assert_eq!(policies.active(999).count(), 0);
+        assert_eq!(policies.history("gold").len(), 1);
+        assert_eq!(
+            policies.get("gold", 1000).map(|policy| policy.version),
+            Some(1)
+        );
+    }
+
+    #[test]
+    fn versions_must_take_effect_strictly_in_order() {
+        let same_time = load(&["", "version = 2"], &HolidayCalendars::default()).unwrap_err();
+        assert_eq!(
+            same_time.to_string(),
+            "versions of policy gold must take effect in version order, each strictly after the \
+             previous one"
+        );
+
+        let out_of_order = load(
+            &[
+                "effective_from = 2000",
+                "version = 2\neffective_from = 1000",
+            ],
+            &HolidayCalendars::default(),
+        )
+        .unwrap_err();
+        assert_eq!(out_of_order.to_string(), same_time.to_string());
+
+        let duplicate = load(&["", ""], &HolidayCalendars::default()).unwrap_err();
+        assert_eq!(duplicate.to_string(), "duplicate policy gold version 1");
+    }
+
+    #[test]
+    fn warnings_do_not_stop_loading() {
+        let capped = r#"max_portfolio_exposure = "100_000 mUSD""#;
+        assert!(load(&[capped], &HolidayCalendars::default()).is_ok());
+
+        let unsatisfiable = r#"eligibility = "accredited AND false""#;
+        let error = load(&[unsatisfiable], &HolidayCalendars::default()).unwrap_err();
+        assert!(
+            error
+                .to_string()
+                .starts_with("policy set has 1 problem(s):"),
+            "{error}"
+        );
+    }
 }
@@ -0,0 +1,115 @@
+use anyhow::{Context, Result};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use crate::types::{Action, Amount, PoolId};
+use crate::utils::write_atomically;
+
+pub const DEFAULT_POSITIONS_PATH: &str = "data/positions.json";
+
+/// Each user's position per pool as proven by the host's own approvals, persisted as a JSON
+/// file.
+///
+/// Approved buys and deposits add to it and approved exits take from it. Exits of the whole
+/// proven position are exempt from the trade size floors, so it is never taken from the
+/// client-supplied `exposure_musd`. Users with no approvals recorded have no proven position.
+#[derive(Debug, Clone)]
+pub struct PositionStore {
+    path: PathBuf,
+    positions: Arc<Mutex<BTreeMap<PoolId, BTreeMap<String, Amount>>>>,
+}
+
+impl PositionStore {
+    /// Opens the store at `path`, starting empty if the file does not exist yet.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let positions: BTreeMap<PoolId, BTreeMap<String, Amount>> = if path.exists() {
+            let raw = fs::read_to_string(&path)
+                .with_context(|| format!("failed to read positions {}", path.display()))?;
+            serde_json::from_str(&raw)
+                .with_context(|| format!("invalid positions {}", path.display()))?
+        } else {
+            BTreeMap::new()
+        };
+        println!(
+            "✅ Loaded positions in {} pools from {}",
+            positions.len(),
+            path.display()
+        );
+
+        Ok(Self {
+            path,
+            positions: Arc::new(Mutex::new(positions)),
+        })
+    }
+
+    /// `user`'s proven position in `pool_id`, if any approval is recorded for it.
+    pub fn position(&self, user: &str, pool_id: &PoolId) -> Option<Amount> {
+        self.positions
+            .lock()
+            .unwrap()
+            .get(pool_id)?
+            .get(&user.to_ascii_lowercase())
+            .copied()
+    }
+
+    /// Applies an approved `action` for `amount` to `user`'s position in `pool_id` and returns
+    /// the new position. An exit never takes it below zero.
+    pub fn apply(
+        &self,
+        user: &str,
+        pool_id: &PoolId,
+        action: Action,
+        amount: Amount,
+    ) -> Result<Amount> {
+        let mut positions = self.positions.lock().unwrap();
+        let position = positions
+            .entry(pool_id.clone())
+            .or_default()
+            .entry(user.to_ascii_lowercase())
+            .or_insert(Amount::ZERO);
+        *position = if action.increases_exposure() {
+            position.saturating_add(amount)
+        } else {
+            position.saturating_sub(amount)
+        };
+        let updated = *position;
+        write_atomically(&self.path, &serde_json::to_vec_pretty(&*positions)?)?;
+        Ok(updated)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::temp_path;
+
+    #[test]
+    fn approvals_add_up_and_exits_floor_at_zero() {
+        let positions = PositionStore::load(temp_path("positions")).unwrap();
+        let gold = PoolId::new("gold");
+        assert_eq!(positions.position("0xabc", &gold), None);
+
+        let deposit = Amount::from_musd(30_000);
+        positions
+            .apply("0xABC", &gold, Action::Buy, Amount::from_musd(20_000))
+            .unwrap();
+        positions
+            .apply("0xabc", &gold, Action::AddLiquidity, deposit)
+            .unwrap();
+        assert_eq!(
+            positions.position("0xabc", &gold),
+            Some(Amount::from_musd(50_000))
+        );
+
+        let left = positions
+            .apply("0xabc", &gold, Action::Sell, Amount::from_musd(80_000))
+            .unwrap();
+        assert_eq!(left, Amount::ZERO);
+
+        let reloaded = PositionStore::load(&positions.path).unwrap();
+        assert_eq!(reloaded.position("0xabc", &gold), Some(Amount::ZERO));
+    }
+}
@@ -1,4 +1,6 @@
-use crate::types::{ComplianceOutcome, ComplianceRequest, ProofMetadata, UserResponse};
+use crate::types::{
+    ComplianceInput, ComplianceOutcome, FromJournal, ProofMetadata, ToGuestStdin, UserResponse,
+};
 use alloy::signers::local::PrivateKeySigner;
 use axum::extract::Json;
 use boundless_market::{request_builder::OfferParams, Client};
@@ -9,7 +11,7 @@ pub async fn submit_proof_request(
     signer: &PrivateKeySigner,
     rpc_url: Url,
     guest_program_url: Url,
-    Json(payload): Json<ComplianceRequest>,
+    Json(input): Json<ComplianceInput>,
 ) -> anyhow::Result<Json<UserResponse>> {
     let client = Client::builder()
         .with_rpc_url(rpc_url)
@@ -19,7 +21,7 @@ pub async fn submit_proof_request(
 
     println!("🚀 Submitting Boundless request");
     println!("📦 Using guest ELF from: {}", guest_program_url);
-    let stdin = payload.to_guest_stdin()?;
+    let stdin = input.to_guest_stdin()?;
 
     let request = client
         .new_request()
@@ -51,9 +53,10 @@ pub async fn submit_proof_request(
         .ok_or_else(|| anyhow::anyhow!("No journal in fulfillment"))?;
 
     let outcome = ComplianceOutcome::from_journal(journal_bytes.as_ref())?;
+    let violation_codes: Vec<&str> = outcome.violations.iter().map(|v| v.code()).collect();
     println!(
-        "Compliance decision: pool={} allowed={} reason={}",
-        outcome.pool_id, outcome.allowed, outcome.reason
+        "Compliance decision: pool={} allowed={} violations={:?} reason={}",
+        outcome.pool_id, outcome.allowed, violation_codes, outcome.reason
     );
 
     let proof = ProofMetadata::new(
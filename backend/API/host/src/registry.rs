@@ -0,0 +1,303 @@
+use anyhow::{bail, ensure, Context, Result};
+use serde::Deserialize;
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::holidays::HolidayCalendars;
+use crate::policy::PolicySet;
+use crate::types::{ComplianceRequest, PoolEntry, PoolId, PoolPolicy};
+
+pub const DEFAULT_POLICIES_PATH: &str = "policies/pools.toml";
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RegistryFile {
+    #[serde(default)]
+    policies: Vec<PoolPolicy>,
+    #[serde(default)]
+    pools: Vec<PoolEntry>,
+}
+
+/// Pools the API serves, each mapped to the policy it is evaluated under.
+///
+/// Pools are looked up by registry key or by on-chain address, so a pool created through
+/// `RWAPoolFactory.createPool` only needs a config entry, not a new build.
+#[derive(Debug, Clone)]
+pub struct PoolRegistry {
+    pools: Vec<PoolEntry>,
+    policies: PolicySet,
+}
+
+impl PoolRegistry {
+    /// Loads policies and pools from `path`, which is either a single `.toml`/`.json` file or
+    /// a directory of them, and validates the result against `calendars`.
+    pub fn load(path: impl AsRef<Path>, calendars: &HolidayCalendars) -> Result<Self> {
+        let path = path.as_ref();
+        let mut merged = RegistryFile::default();
+        for file in registry_files(path)? {
+            let raw = fs::read_to_string(&file)
+                .with_context(|| format!("failed to read policy file {}", file.display()))?;
+            let parsed: RegistryFile = match file.extension().and_then(|ext| ext.to_str()) {
+                Some("toml") => toml::from_str(&raw)
+                    .with_context(|| format!("invalid TOML in {}", file.display()))?,
+                Some("json") => serde_json::from_str(&raw)
+                    .with_context(|| format!("invalid JSON in {}", file.display()))?,
+                _ => bail!("unsupported policy file {}", file.display()),
+            };
+            merged.policies.extend(parsed.policies);
+            merged.pools.extend(parsed.pools);
+        }
+
+        let registry = Self::new(merged.pools, PolicySet::new(merged.policies, calendars)?)?;
+        println!(
+            "✅ Loaded {} pools and {} policies from {}",
+            registry.pools.len(),
+            registry.policies.all().len(),
+            path.display()
+        );
+        Ok(registry)
+    }
+
+    pub fn new(pools: Vec<PoolEntry>, policies: PolicySet) -> Result<Self> {
+        ensure!(!pools.is_empty(), "no pools configured");
+
+        let mut seen = HashSet::new();
+        let mut normalized = Vec::with_capacity(pools.len());
+        for mut pool in pools {
+            pool.pool_id = PoolId::new(pool.pool_id.as_str().trim().to_ascii_lowercase());
+            ensure!(
+                !pool.pool_id.as_str().is_empty(),
+                "pool has an empty pool_id"
+            );
+            ensure!(
+                !pool.name.trim().is_empty(),
+                "pool {} has an empty name",
+                pool.pool_id
+            );
+            ensure!(
+                !pool.asset_symbol.trim().is_empty(),
+                "pool {} has an empty asset_symbol",
+                pool.pool_id
+            );
+            ensure!(
+                !policies.history(&pool.policy).is_empty(),
+                "pool {} references unknown policy {}",
+                pool.pool_id,
+                pool.policy
+            );
+            if let Some(shadow) = &pool.shadow_policy {
+                ensure!(
+                    !policies.history(shadow).is_empty(),
+                    "pool {} references unknown shadow policy {}",
+                    pool.pool_id,
+                    shadow
+                );
+                ensure!(
+                    *shadow != pool.policy,
+                    "pool {} shadows its own policy {}; name a candidate policy instead",
+                    pool.pool_id,
+                    shadow
+                );
+            }
+            ensure!(
+                seen.insert(pool.pool_id.as_str().to_string()),
+                "duplicate pool {}",
+                pool.pool_id
+            );
+            if let Some(address) = pool.address.as_mut() {
+                *address = address.trim().to_ascii_lowercase();
+                ensure!(
+                    is_address(address),
+                    "pool {} has an invalid address {}",
+                    pool.pool_id,
+                    address
+                );
+                ensure!(
+                    seen.insert(address.clone()),
+                    "address {} is registered twice",
+                    address
+                );
+            }
+            normalized.push(pool);
+        }
+
+        Ok(Self {
+            pools: normalized,
+            policies,
+        })
+    }
+
+    /// Finds a pool by registry key or on-chain address.
+    pub fn pool(&self, pool_id: &PoolId) -> Option<&PoolEntry> {
+        self.pools.iter().find(|pool| {
+            pool.pool_id.matches(pool_id.as_str())
+                || pool
+                    .address
+                    .as_deref()
+                    .is_some_and(|address| pool_id.matches(address))
+        })
+    }
+
+    /// Resolves a pool together with the version of its policy in force at `now`; `None`
+    /// also when no version has taken effect yet.
+    pub fn resolve(&self, pool_id: &PoolId, now: u64) -> Option<(&PoolEntry, &PoolPolicy)> {
+        let pool = self.pool(pool_id)?;
+        let policy = self.policies.get(&pool.policy, now)?;
+        Some((pool, policy))
+    }
+
+    /// The version of `pool`'s candidate policy in force at `now`, if it has one.
+    pub fn shadow_policy(&self, pool: &PoolEntry, now: u64) -> Option<&PoolPolicy> {
+        self.policies.get(pool.shadow_policy.as_deref()?, now)
+    }
+
+    /// Resolves `request`'s pool and rewrites it, and every portfolio entry naming a registered
+    /// pool, to the registry key. Requests may name pools by address; the journal always
+    /// carries the key.
+    pub fn resolve_request(
+        &self,
+        request: &mut ComplianceRequest,
+        now: u64,
+    ) -> Option<(&PoolEntry, &PoolPolicy)> {
+        let (pool, policy) = self.resolve(&request.pool_id, now)?;
+        request.pool_id = pool.pool_id.clone();
+        for position in &mut request.portfolio {
+            if let Some(entry) = self.pool(&position.pool_id) {
+                position.pool_id = entry.pool_id.clone();
+            }
+        }
+        Some((pool, policy))
+    }
+
+    pub fn pools(&self) -> &[PoolEntry] {
+        &self.pools
+    }
+
+    pub fn policies(&self) -> &PolicySet {
+        &self.policies
+    }
+}
+
+fn registry_files(path: &Path) -> Result<Vec<PathBuf>> {
+    if path.is_file() {
+        return Ok(vec![path.to_path_buf()]);
+    }
+
+    let mut files = Vec::new();
+    for entry in fs::read_dir(path)
+        .with_context(|| format!("failed to read policy directory {}", path.display()))?
+    {
+        let file = entry?.path();
+        if matches!(
+            file.extension().and_then(|ext| ext.to_str()),
+            Some("toml" | "json")
+        ) {
+            files.push(file);
+        }
+    }
+    files.sort();
+    ensure!(!files.is_empty(), "no policy files in {}", path.display());
+    Ok(files)
+}
+
+pub fn is_address(value: &str) -> bool {
+    value
+        .strip_prefix("0x")
+        .is_some_and(|hex| hex.len() == 40 && hex.bytes().all(|b| b.is_ascii_hexdigit()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::policy::evaluate;
+    use crate::test_support::{self, POLICY};
+    use crate::types::{Amount, EvaluationContext, PoolExposure};
+
+    const GOLD_ADDRESS: &str = "0x4abd994dd8e6581d909a6acef82e453d3e141d65";
+    const SILVER_ADDRESS: &str = "0x1111222233334444555566667777888899990000";
+
+    fn registry() -> PoolRegistry {
+        let raw = format!(
+            r#"
+            [[policies]]
+            {POLICY}
+
+            [[pools]]
+            pool_id = "gold"
+            address = "{GOLD_ADDRESS}"
+            name = "Gold"
+            asset_symbol = "GLD"
+            policy = "gold"
+
+            [[pools]]
+            pool_id = "silver"
+            address = "{SILVER_ADDRESS}"
+            name = "Silver"
+            asset_symbol = "SLV"
+            policy = "gold"
+            "#
+        );
+        let file: RegistryFile = toml::from_str(&raw).unwrap();
+        let policies = PolicySet::new(file.policies, &HolidayCalendars::default()).unwrap();
+        PoolRegistry::new(file.pools, policies).unwrap()
+    }
+
+    fn request(pool_id: &str, portfolio: Vec<PoolExposure>) -> ComplianceRequest {
+        ComplianceRequest {
+            pool_id: PoolId::new(pool_id),
+            portfolio,
+            ..test_support::request()
+        }
+    }
+
+    fn position(pool_id: &str, musd: u64) -> PoolExposure {
+        PoolExposure {
+            pool_id: PoolId::new(pool_id),
+            exposure_musd: Amount::from_musd(musd),
+        }
+    }
+
+    #[test]
+    fn resolve_request_rewrites_addresses_to_keys() {
+        let registry = registry();
+        let mut request = request(
+            &GOLD_ADDRESS.to_ascii_uppercase().replacen("0X", "0x", 1),
+            vec![
+                position(GOLD_ADDRESS, 90_000),
+                position(SILVER_ADDRESS, 30_000),
+                position("unregistered", 5_000),
+            ],
+        );
+
+        let (pool, _) = registry.resolve_request(&mut request, 0).unwrap();
+        assert_eq!(pool.pool_id, PoolId::new("gold"));
+        assert_eq!(request.pool_id, PoolId::new("gold"));
+        let keys: Vec<&str> = request
+            .portfolio
+            .iter()
+            .map(|position| position.pool_id.as_str())
+            .collect();
+        assert_eq!(keys, ["gold", "silver", "unregistered"]);
+    }
+
+    #[test]
+    fn portfolio_cap_counts_the_requested_pool_once_when_named_by_address() {
+        let registry = registry();
+        let mut policy = registry.policies().get("gold", 0).unwrap().clone();
+        policy.max_portfolio_exposure = Some(Amount::from_musd(60_000));
+        let mut request = request(
+            "gold",
+            vec![
+                position(GOLD_ADDRESS, 90_000),
+                position(SILVER_ADDRESS, 30_000),
+            ],
+        );
+
+        registry.resolve_request(&mut request, 0).unwrap();
+        let outcome = evaluate(&request, &policy, &EvaluationContext::default());
+        assert!(outcome.allowed, "{}", outcome.reason);
+        assert_eq!(outcome.portfolio_exposure_musd, Amount::from_musd(50_000));
+        assert_eq!(outcome.max_allocation, Amount::from_musd(10_000));
+    }
+}
@@ -0,0 +1,369 @@
+use crate::policy::evaluate;
+use crate::types::{
+    Amount, ComplianceOutcome, ComplianceRequest, EvaluationContext, PoolEligibility, PoolExposure,
+    PoolPolicy, Remediation, RemediationResponse, RemediationStep, Violation,
+};
+use crate::utils::{unix_now, AppState};
+
+/// Turns the violations in `outcome` into the changes that would make the request pass.
+pub fn remediate(
+    request: &ComplianceRequest,
+    policy: &PoolPolicy,
+    context: &EvaluationContext,
+    outcome: &ComplianceOutcome,
+) -> Remediation {
+    let max_amount = policy.max_amount(request, context);
+    let mut steps = Vec::new();
+    let mut amount_step_added = false;
+    let mut minimum_step_added = false;
+    let mut trading_step_added = false;
+
+    for violation in &outcome.violations {
+        let step = match violation {
+            Violation::AddressDenied => RemediationStep::AddressBlocked,
+            Violation::AmlFailed => RemediationStep::PassAmlScreening,
+            Violation::ResidencyMissing | Violation::ResidencyInvalid { .. } => {
+                RemediationStep::ProvideResidency
+            }
+            Violation::ResidencyNotAllowed { residency }
+            | Violation::ResidencyBanned { residency } => RemediationStep::ResidencyIneligible {
+                residency: residency.clone(),
+            },
+            Violation::KycLevelTooLow { required, .. } => RemediationStep::RaiseKycLevel {
+                required: *required,
+            },
+            Violation::RiskScoreTooHigh { limit, .. } => {
+                RemediationStep::LowerRiskScore { max: *limit }
+            }
+            Violation::AccreditationRequired => RemediationStep::ObtainAccreditation,
+            Violation::InvestorTypeNotAllowed { .. } => RemediationStep::InvestorTypeIneligible {
+                allowed: policy.allowed_investor_types.clone(),
+            },
+            Violation::InvestorCategoriesMissing { missing } => {
+                RemediationStep::ObtainInvestorCategories {
+                    categories: missing.clone(),
+                }
+            }
+            Violation::LockedUp { unlocks_at } => RemediationStep::WaitForUnlock {
+                unlocks_at: *unlocks_at,
+            },
+            Violation::InvestorCapReached { .. } => RemediationStep::InvestorCapReached,
+            Violation::BelowMinTrade { .. } | Violation::BelowMinInitialInvestment { .. } => {
+                // Both floors are fixed by the same change.
+                if minimum_step_added {
+                    continue;
+                }
+                minimum_step_added = true;
+                RemediationStep::IncreaseAmount {
+                    min_amount: policy.min_amount(request),
+                }
+            }
+            Violation::OutsideTradingWindow { .. } | Violation::MarketHoliday { .. } => {
+                if trading_step_added {
+                    continue;
+                }
+                trading_step_added = true;
+                RemediationStep::WaitForTradingWindow {
+                    opens_at: policy.next_trading_time(context.now),
+                }
+            }
+            Violation::EligibilityRuleFailed { rule } => {
+                RemediationStep::EligibilityRuleFailed { rule: rule.clone() }
+            }
+            Violation::NotLotMultiple { lot_size, .. } => RemediationStep::RoundToLot {
+                lot_size: *lot_size,
+            },
+            Violation::SingleTradeLimitExceeded { .. }
+            | Violation::ExposureCapExceeded { .. }
+            | Violation::VolumeLimitExceeded { .. }
+            | Violation::PortfolioCapExceeded { .. } => {
+                // Every amount violation is fixed by the same change.
+                if amount_step_added {
+                    continue;
+                }
+                amount_step_added = true;
+                if !max_amount.is_zero() {
+                    RemediationStep::ReduceAmount { max_amount }
+                } else if let Some(window) = exhausted_window(policy, context) {
+                    RemediationStep::VolumeWindowExhausted {
+                        period_days: window,
+                    }
+                } else if portfolio_exhausted(request, policy) {
+                    RemediationStep::PortfolioCapReached
+                } else {
+                    RemediationStep::ExposureCapReached
+                }
+            }
+        };
+        steps.push(step);
+    }
+
+    Remediation {
+        // No amount rule applies to this action, so there is no limit to report.
+        max_amount: (max_amount != Amount::MAX).then_some(max_amount),
+        eligible: !outcome.violations.iter().any(is_eligibility_violation),
+        steps,
+    }
+}
+
+/// Remediation for the requested pool plus eligibility for every other registered pool.
+///
+/// The other pools are evaluated with the user's exposure there taken from `portfolio`
+/// (none if it has no entry), and the requested pool's exposure moved into the portfolio.
+pub fn remediation_report(
+    state: &AppState,
+    request: &ComplianceRequest,
+) -> Option<RemediationResponse> {
+    let registry = &state.registry;
+    let mut request = request.clone();
+    let now = unix_now();
+    let (pool, policy) = registry.resolve_request(&mut request, now)?;
+
+    let context = state.context(&request.user, &pool.pool_id, policy, now);
+    let outcome = evaluate(&request, policy, &context);
+    let remediation = remediate(&request, policy, &context, &outcome);
+
+    let alternatives = registry
+        .pools()
+        .iter()
+        .filter(|other| other.pool_id != pool.pool_id)
+        .filter_map(|other| {
+            let (other, other_policy) = registry.resolve(&other.pool_id, now)?;
+            let candidate = ComplianceRequest {
+                pool_id: other.pool_id.clone(),
+                exposure_musd: request
+                    .portfolio
+                    .iter()
+                    .find(|position| position.pool_id == other.pool_id)
+                    .map_or(Amount::ZERO, |position| position.exposure_musd),
+                portfolio: request
+                    .portfolio
+                    .iter()
+                    .filter(|position| position.pool_id != other.pool_id)
+                    .cloned()
+                    .chain([PoolExposure {
+                        pool_id: request.pool_id.clone(),
+                        exposure_musd: request.exposure_musd,
+                    }])
+                    .collect(),
+                ..request.clone()
+            };
+            let other_context = state.context(&request.user, &other.pool_id, other_policy, now);
+            let other_outcome = evaluate(&candidate, other_policy, &other_context);
+            Some(PoolEligibility {
+                pool_id: other.pool_id.clone(),
+                name: other.name.clone(),
+                asset_symbol: other.asset_symbol.clone(),
+                allowed: other_outcome.allowed,
+                remediation: remediate(&candidate, other_policy, &other_context, &other_outcome),
+            })
+        })
+        .collect();
+
+    Some(RemediationResponse {
+        outcome,
+        remediation,
+        alternatives,
+    })
+}
+
+/// Whether `violation` is about who the user is, rather than the amount, the timing or the
+/// pool's room, which can all change without the user's profile changing.
+fn is_eligibility_violation(violation: &Violation) -> bool {
+    match violation {
+        Violation::AddressDenied
+        | Violation::AmlFailed
+        | Violation::ResidencyMissing
+        | Violation::ResidencyInvalid { .. }
+        | Violation::ResidencyNotAllowed { .. }
+        | Violation::ResidencyBanned { .. }
+        | Violation::KycLevelTooLow { .. }
+        | Violation::RiskScoreTooHigh { .. }
+        | Violation::AccreditationRequired
+        | Violation::InvestorTypeNotAllowed { .. }
+        | Violation::InvestorCategoriesMissing { .. }
+        | Violation::EligibilityRuleFailed { .. } => true,
+        Violation::SingleTradeLimitExceeded { .. }
+        | Violation::ExposureCapExceeded { .. }
+        | Violation::VolumeLimitExceeded { .. }
+        | Violation::PortfolioCapExceeded { .. }
+        | Violation::BelowMinTrade { .. }
+        | Violation::NotLotMultiple { .. }
+        | Violation::BelowMinInitialInvestment { .. }
+        | Violation::LockedUp { .. }
+        | Violation::InvestorCapReached { .. }
+        | Violation::OutsideTradingWindow { .. }
+        | Violation::MarketHoliday { .. } => false,
+    }
+}
+
+fn portfolio_exhausted(request: &ComplianceRequest, policy: &PoolPolicy) -> bool {
+    policy
+        .portfolio_cap(request)
+        .is_some_and(|cap| request.portfolio_exposure() >= cap)
+}
+
+/// The shortest rolling window with nothing left, if any.
+fn exhausted_window(policy: &PoolPolicy, context: &EvaluationContext) -> Option<u32> {
+    policy
+        .volume_windows
+        .iter()
+        .filter(|window| context.window_used(window.period_days) >= window.max_volume)
+        .map(|window| window.period_days)
+        .min()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{policy, request};
+    use crate::types::{Action, Date, TradingWindow};
+
+    const DAY: u64 = 24 * 60 * 60;
+
+    fn check(
+        request: &ComplianceRequest,
+        policy: &PoolPolicy,
+        context: &EvaluationContext,
+    ) -> (Vec<Violation>, Remediation) {
+        let outcome = evaluate(request, policy, context);
+        let remediation = remediate(request, policy, context, &outcome);
+        (outcome.violations, remediation)
+    }
+
+    #[test]
+    fn amount_violations_keep_the_user_eligible() {
+        let request = ComplianceRequest {
+            requested_amount: Amount::from_musd(60_000),
+            ..request()
+        };
+        let (violations, remediation) = check(&request, &policy(), &EvaluationContext::default());
+
+        assert!(matches!(
+            violations[..],
+            [Violation::SingleTradeLimitExceeded { .. }]
+        ));
+        assert!(remediation.eligible);
+        assert!(matches!(
+            remediation.steps[..],
+            [RemediationStep::ReduceAmount { max_amount }] if max_amount == Amount::from_musd(50_000)
+        ));
+    }
+
+    #[test]
+    fn profile_violations_make_the_user_ineligible() {
+        let request = ComplianceRequest {
+            kyc_level: 1,
+            ..request()
+        };
+        let (violations, remediation) = check(&request, &policy(), &EvaluationContext::default());
+
+        assert!(matches!(violations[..], [Violation::KycLevelTooLow { .. }]));
+        assert!(!remediation.eligible);
+    }
+
+    #[test]
+    fn lock_up_keeps_the_user_eligible() {
+        let policy = PoolPolicy {
+            lockup_days: Some(30),
+            ..policy()
+        };
+        let request = ComplianceRequest {
+            action: Action::Sell,
+            ..request()
+        };
+        let now = 100 * DAY;
+        let context = EvaluationContext {
+            now,
+            last_acquired_at: Some(now - DAY),
+            ..EvaluationContext::default()
+        };
+        let (violations, remediation) = check(&request, &policy, &context);
+
+        assert!(matches!(violations[..], [Violation::LockedUp { .. }]));
+        assert!(remediation.eligible);
+        assert!(matches!(
+            remediation.steps[..],
+            [RemediationStep::WaitForUnlock { unlocks_at }] if unlocks_at == now + 29 * DAY
+        ));
+    }
+
+    #[test]
+    fn full_pool_keeps_the_user_eligible() {
+        let policy = PoolPolicy {
+            max_investors: Some(1),
+            ..policy()
+        };
+        let context = EvaluationContext {
+            investor_count: 1,
+            ..EvaluationContext::default()
+        };
+        let (violations, remediation) = check(&request(), &policy, &context);
+
+        assert!(matches!(
+            violations[..],
+            [Violation::InvestorCapReached { .. }]
+        ));
+        assert!(remediation.eligible);
+    }
+
+    #[test]
+    fn closed_market_keeps_the_user_eligible() {
+        let policy = PoolPolicy {
+            trading_windows: vec![TradingWindow {
+                weekdays: Vec::new(),
+                opens: "09:00".parse().unwrap(),
+                closes: "14:00".parse().unwrap(),
+            }],
+            holidays: vec!["2026-01-19".parse().unwrap()],
+            ..policy()
+        };
+        let holiday: Date = "2026-01-19".parse().unwrap();
+        let context = |now| EvaluationContext {
+            now,
+            ..EvaluationContext::default()
+        };
+
+        let evening = holiday.start() - 6 * 60 * 60;
+        let (violations, remediation) = check(&request(), &policy, &context(evening));
+        assert!(matches!(
+            violations[..],
+            [Violation::OutsideTradingWindow { .. }]
+        ));
+        assert!(remediation.eligible);
+
+        let (violations, remediation) = check(
+            &request(),
+            &policy,
+            &context(holiday.start() + 10 * 60 * 60),
+        );
+        assert!(matches!(violations[..], [Violation::MarketHoliday { .. }]));
+        assert!(remediation.eligible);
+        // The next opening skips the holiday.
+        assert!(matches!(
+            remediation.steps[..],
+            [RemediationStep::WaitForTradingWindow { opens_at: Some(opens_at) }]
+                if opens_at == holiday.start() + DAY + 9 * 60 * 60
+        ));
+    }
+
+    #[test]
+    fn profile_violation_outweighs_a_timing_one() {
+        let policy = PoolPolicy {
+            max_investors: Some(1),
+            ..policy()
+        };
+        let request = ComplianceRequest {
+            aml_passed: false,
+            ..request()
+        };
+        let context = EvaluationContext {
+            investor_count: 1,
+            ..EvaluationContext::default()
+        };
+        let (violations, remediation) = check(&request, &policy, &context);
+
+        assert_eq!(violations.len(), 2);
+        assert!(!remediation.eligible);
+    }
+}
@@ -0,0 +1,396 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use utoipa::ToSchema;
+
+use crate::types::{Action, Amount, ComplianceOutcome, PolicyHash, PoolId, PoolPolicy, Violation};
+use crate::utils::write_atomically;
+
+pub const DEFAULT_SHADOW_PATH: &str = "data/shadow.json";
+
+/// How many of the latest divergences each pool keeps.
+const MAX_DIVERGENCES: usize = 100;
+
+/// How many of the latest request keys each pool remembers to count retries once.
+const MAX_SEEN: usize = 10_000;
+
+/// How often recorded outcomes are written to disk.
+pub const SHADOW_FLUSH_INTERVAL: Duration = Duration::from_secs(10);
+
+/// A live request the candidate policy would have decided differently.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct Divergence {
+    #[schema(example = "0x2111222233334444555566667777888899990000")]
+    pub user: String,
+    pub action: Action,
+    #[schema(example = "10000000000")]
+    pub requested_amount: Amount,
+    /// Unix seconds the request was evaluated at.
+    #[schema(example = 1767279600)]
+    pub evaluated_at: u64,
+    #[schema(example = true)]
+    pub live_allowed: bool,
+    #[schema(example = false)]
+    pub candidate_allowed: bool,
+    #[schema(example = "10000000000")]
+    pub live_approved_amount: Amount,
+    #[schema(example = "0")]
+    pub candidate_approved_amount: Amount,
+    /// Rules the request fails under the candidate policy.
+    pub candidate_violations: Vec<Violation>,
+}
+
+/// How a pool's candidate policy would have decided the live requests seen so far.
+///
+/// Counts restart whenever the candidate changes, so they always describe the policy named.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ShadowReport {
+    #[schema(value_type = String, example = "gold")]
+    pub pool_id: PoolId,
+    /// `policy_id` of the candidate policy.
+    #[schema(example = "gold_strict")]
+    pub candidate_policy: String,
+    #[schema(example = 1)]
+    pub candidate_version: u32,
+    #[schema(
+        value_type = String,
+        example = "0x9f2c4e0d6b1a8f3e5c7d9b2a4f6e8c0d1b3a5f7e9c2d4b6a8f0e1c3d5b7a9f2c"
+    )]
+    pub candidate_hash: PolicyHash,
+    /// Live requests evaluated under both policies. A retry of the same input counts once
+    /// while it is among the pool's latest 10 000 inputs since the server started; after a
+    /// restart it counts again.
+    #[schema(example = 1200)]
+    pub evaluated: u64,
+    /// Requests the live policy allows and the candidate would deny.
+    #[schema(example = 37)]
+    pub allow_to_deny: u64,
+    /// Requests the live policy denies and the candidate would allow.
+    #[schema(example = 0)]
+    pub deny_to_allow: u64,
+    /// Requests both allow, but for a different approved amount.
+    #[schema(example = 4)]
+    pub amount_changed: u64,
+    /// The latest divergences, oldest first.
+    pub divergences: Vec<Divergence>,
+}
+
+/// Outcomes of candidate policies evaluated alongside the live ones, persisted as a JSON file.
+///
+/// Only divergences are kept in full, and only the latest per pool; everything else is
+/// counted. Recording only touches memory; [`ShadowStore::flush`] writes the reports out,
+/// and [`ShadowStore::spawn_flusher`] does so periodically off the request path.
+#[derive(Debug, Clone)]
+pub struct ShadowStore {
+    path: PathBuf,
+    state: Arc<Mutex<ShadowState>>,
+}
+
+#[derive(Debug, Default)]
+struct ShadowState {
+    reports: BTreeMap<PoolId, ShadowReport>,
+    /// Cache keys of the requests already counted per pool, so retries count once.
+    seen: HashMap<PoolId, SeenKeys>,
+    /// Whether `reports` changed since the last flush.
+    dirty: bool,
+}
+
+/// The latest [`MAX_SEEN`] keys, forgetting the oldest first.
+#[derive(Debug, Default)]
+struct SeenKeys {
+    keys: HashSet<String>,
+    order: VecDeque<String>,
+}
+
+impl SeenKeys {
+    /// Remembers `key`, returning whether it was new.
+    fn insert(&mut self, key: &str) -> bool {
+        if !self.keys.insert(key.to_string()) {
+            return false;
+        }
+        self.order.push_back(key.to_string());
+        if self.order.len() > MAX_SEEN {
+            if let Some(oldest) = self.order.pop_front() {
+                self.keys.remove(&oldest);
+            }
+        }
+        true
+    }
+
+    fn clear(&mut self) {
+        self.keys.clear();
+        self.order.clear();
+    }
+}
+
+impl ShadowStore {
+    /// Opens the store at `path`, starting empty if the file does not exist yet.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let reports: BTreeMap<PoolId, ShadowReport> = if path.exists() {
+            let raw = fs::read_to_string(&path)
+                .with_context(|| format!("failed to read shadow reports {}", path.display()))?;
+            serde_json::from_str(&raw)
+                .with_context(|| format!("invalid shadow reports {}", path.display()))?
+        } else {
+            BTreeMap::new()
+        };
+        println!(
+            "✅ Loaded shadow reports of {} pools from {}",
+            reports.len(),
+            path.display()
+        );
+
+        Ok(Self {
+            path,
+            state: Arc::new(Mutex::new(ShadowState {
+                reports,
+                ..ShadowState::default()
+            })),
+        })
+    }
+
+    pub fn all(&self) -> Vec<ShadowReport> {
+        self.state
+            .lock()
+            .unwrap()
+            .reports
+            .values()
+            .cloned()
+            .collect()
+    }
+
+    /// Compares the live outcome of a request with the `candidate` policy's outcome for it.
+    ///
+    /// `key` identifies the request's input; a request already counted under the same
+    /// candidate is ignored. Returns whether the request was counted.
+    pub fn record(
+        &self,
+        key: &str,
+        candidate: &PoolPolicy,
+        live: &ComplianceOutcome,
+        shadow: &ComplianceOutcome,
+    ) -> bool {
+        let candidate_hash = candidate.policy_hash();
+        let fresh = || ShadowReport {
+            pool_id: live.pool_id.clone(),
+            candidate_policy: candidate.policy_id.clone(),
+            candidate_version: candidate.version,
+            candidate_hash,
+            evaluated: 0,
+            allow_to_deny: 0,
+            deny_to_allow: 0,
+            amount_changed: 0,
+            divergences: Vec::new(),
+        };
+        let mut state = self.state.lock().unwrap();
+        let ShadowState {
+            reports,
+            seen,
+            dirty,
+        } = &mut *state;
+        let report = reports.entry(live.pool_id.clone()).or_insert_with(fresh);
+        let seen = seen.entry(live.pool_id.clone()).or_default();
+        if report.candidate_hash != candidate_hash {
+            *report = fresh();
+            seen.clear();
+        }
+        if !seen.insert(key) {
+            return false;
+        }
+
+        report.evaluated += 1;
+        match (live.allowed, shadow.allowed) {
+            (true, false) => report.allow_to_deny += 1,
+            (false, true) => report.deny_to_allow += 1,
+            (true, true) if live.approved_amount != shadow.approved_amount => {
+                report.amount_changed += 1
+            }
+            _ => {}
+        }
+        let diverged =
+            live.allowed != shadow.allowed || live.approved_amount != shadow.approved_amount;
+        if diverged {
+            report.divergences.push(Divergence {
+                user: live.user.clone(),
+                action: live.action,
+                requested_amount: live.requested_amount,
+                evaluated_at: live.evaluated_at,
+                live_allowed: live.allowed,
+                candidate_allowed: shadow.allowed,
+                live_approved_amount: live.approved_amount,
+                candidate_approved_amount: shadow.approved_amount,
+                candidate_violations: shadow.violations.clone(),
+            });
+            let excess = report.divergences.len().saturating_sub(MAX_DIVERGENCES);
+            report.divergences.drain(..excess);
+        }
+        *dirty = true;
+        true
+    }
+
+    /// Writes the reports to disk if they changed since the last flush.
+    pub fn flush(&self) -> Result<()> {
+        let raw = {
+            let mut state = self.state.lock().unwrap();
+            if !state.dirty {
+                return Ok(());
+            }
+            state.dirty = false;
+            serde_json::to_vec_pretty(&state.reports)?
+        };
+        write_atomically(&self.path, &raw).inspect_err(|_| {
+            self.state.lock().unwrap().dirty = true;
+        })
+    }
+
+    /// Flushes the reports every `interval` on the blocking pool until the process exits.
+    pub fn spawn_flusher(&self, interval: Duration) {
+        let store = self.clone();
+        tokio::spawn(async move {
+            let mut ticks = tokio::time::interval(interval);
+            loop {
+                ticks.tick().await;
+                let store = store.clone();
+                match tokio::task::spawn_blocking(move || store.flush()).await {
+                    Ok(Ok(())) => {}
+                    Ok(Err(e)) => eprintln!("❌ Failed to persist shadow reports: {:?}", e),
+                    Err(e) => eprintln!("❌ Shadow flush task failed: {:?}", e),
+                }
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::policy::evaluate;
+    use crate::test_support::{policy, request, temp_path};
+    use crate::types::EvaluationContext;
+
+    fn outcomes() -> (ComplianceOutcome, ComplianceOutcome) {
+        let allowed = evaluate(&request(), &policy(), &EvaluationContext::default());
+        let strict = PoolPolicy {
+            min_kyc_level: 3,
+            ..policy()
+        };
+        let denied = evaluate(&request(), &strict, &EvaluationContext::default());
+        assert!(allowed.allowed && !denied.allowed);
+        (allowed, denied)
+    }
+
+    fn candidate(version: u32) -> PoolPolicy {
+        PoolPolicy {
+            policy_id: "gold_strict".into(),
+            version,
+            ..policy()
+        }
+    }
+
+    #[test]
+    fn counts_each_kind_of_divergence() {
+        let shadow = ShadowStore::load(temp_path("shadow-counts")).unwrap();
+        let candidate = candidate(1);
+        let (allowed, denied) = outcomes();
+        let mut smaller = allowed.clone();
+        smaller.approved_amount = Amount::from_musd(5_000);
+
+        assert!(shadow.record("a", &candidate, &allowed, &denied));
+        assert!(shadow.record("b", &candidate, &denied, &allowed));
+        assert!(shadow.record("c", &candidate, &allowed, &smaller));
+        assert!(shadow.record("d", &candidate, &allowed, &allowed));
+
+        let [report] = shadow.all().try_into().unwrap();
+        assert_eq!(report.evaluated, 4);
+        assert_eq!(report.allow_to_deny, 1);
+        assert_eq!(report.deny_to_allow, 1);
+        assert_eq!(report.amount_changed, 1);
+        assert_eq!(report.divergences.len(), 3);
+        assert!(!report.divergences[0].candidate_allowed);
+        assert!(!report.divergences[0].candidate_violations.is_empty());
+    }
+
+    #[test]
+    fn retries_of_the_same_input_count_once() {
+        let shadow = ShadowStore::load(temp_path("shadow-retries")).unwrap();
+        let (allowed, denied) = outcomes();
+
+        assert!(shadow.record("a", &candidate(1), &allowed, &denied));
+        assert!(!shadow.record("a", &candidate(1), &allowed, &denied));
+
+        let [report] = shadow.all().try_into().unwrap();
+        assert_eq!(report.evaluated, 1);
+        assert_eq!(report.allow_to_deny, 1);
+        assert_eq!(report.divergences.len(), 1);
+    }
+
+    #[test]
+    fn forgets_the_oldest_keys() {
+        let mut seen = SeenKeys::default();
+        for i in 0..MAX_SEEN {
+            assert!(seen.insert(&i.to_string()));
+        }
+        assert!(!seen.insert("0"));
+        assert!(seen.insert("new"));
+        assert_eq!(seen.keys.len(), MAX_SEEN);
+        // The oldest key was dropped to make room, so it counts again, pushing out "1".
+        assert!(seen.insert("0"));
+        assert!(!seen.insert("2"));
+    }
+
+    #[test]
+    fn changed_candidate_restarts_the_counts() {
+        let shadow = ShadowStore::load(temp_path("shadow-reset")).unwrap();
+        let (allowed, denied) = outcomes();
+
+        shadow.record("a", &candidate(1), &allowed, &denied);
+        shadow.record("b", &candidate(1), &allowed, &denied);
+        // The same input is counted again under the new candidate.
+        assert!(shadow.record("a", &candidate(2), &allowed, &allowed));
+
+        let [report] = shadow.all().try_into().unwrap();
+        assert_eq!(report.candidate_version, 2);
+        assert_eq!(report.candidate_hash, candidate(2).policy_hash());
+        assert_eq!(report.evaluated, 1);
+        assert_eq!(report.allow_to_deny, 0);
+        assert!(report.divergences.is_empty());
+    }
+
+    #[test]
+    fn keeps_only_the_latest_divergences() {
+        let shadow = ShadowStore::load(temp_path("shadow-cap")).unwrap();
+        let (allowed, denied) = outcomes();
+
+        for i in 0..MAX_DIVERGENCES + 5 {
+            let mut live = allowed.clone();
+            live.evaluated_at = i as u64;
+            shadow.record(&i.to_string(), &candidate(1), &live, &denied);
+        }
+
+        let [report] = shadow.all().try_into().unwrap();
+        assert_eq!(report.allow_to_deny, MAX_DIVERGENCES as u64 + 5);
+        assert_eq!(report.divergences.len(), MAX_DIVERGENCES);
+        assert_eq!(report.divergences[0].evaluated_at, 5);
+    }
+
+    #[test]
+    fn flush_writes_only_recorded_changes() {
+        let path = temp_path("shadow-flush");
+        let shadow = ShadowStore::load(&path).unwrap();
+        let (allowed, denied) = outcomes();
+
+        shadow.flush().unwrap();
+        assert!(!path.exists());
+        shadow.record("a", &candidate(1), &allowed, &denied);
+        assert!(!path.exists());
+        shadow.flush().unwrap();
+
+        let [report] = ShadowStore::load(&path).unwrap().all().try_into().unwrap();
+        assert_eq!(report.allow_to_deny, 1);
+    }
+}
@@ -0,0 +1,44 @@
+//! Fixtures shared by the host's unit tests.
+
+use std::path::PathBuf;
+
+use crate::types::{ComplianceRequest, PoolPolicy};
+
+/// A policy with every rule at its default, as a pool file would declare it.
+pub const POLICY: &str = r#"
+policy_id = "gold"
+name = "Gold"
+allowed_residencies = ["US", "DE"]
+max_single_trade = "50_000 mUSD"
+max_total_exposure = "150_000 mUSD"
+min_kyc_level = 2
+max_risk_score = 4
+"#;
+
+pub fn policy() -> PoolPolicy {
+    toml::from_str(POLICY).unwrap()
+}
+
+/// A buy into `gold` that [`policy`] allows.
+pub fn request() -> ComplianceRequest {
+    serde_json::from_value(serde_json::json!({
+        "user": "0x2111222233334444555566667777888899990000",
+        "pool_id": "gold",
+        "residency": "US",
+        "kyc_level": 2,
+        "aml_passed": true,
+        "accredited_investor": false,
+        "exposure_musd": "20_000 mUSD",
+        "requested_amount": "10_000 mUSD",
+        "risk_score": 3,
+    }))
+    .unwrap()
+}
+
+/// A path under the system temp directory unique to `name` and this test run, with nothing
+/// at it yet.
+pub fn temp_path(name: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("{}-{}.json", name, std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    path
+}
@@ -1,65 +1,24 @@
 use alloy_primitives::U256;
 use anyhow::Result;
 use risc0_zkvm::serde::{from_slice, to_vec};
-use serde::{Deserialize, Serialize};
-use std::fmt;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use utoipa::ToSchema;
 
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, ToSchema)]
-#[serde(rename_all = "snake_case")]
-pub enum PoolId {
-    Gold,
-    MoneyMarket,
-    RealEstate,
-}
-
-impl fmt::Display for PoolId {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self {
-            PoolId::Gold => write!(f, "Gold"),
-            PoolId::MoneyMarket => write!(f, "Money Market"),
-            PoolId::RealEstate => write!(f, "Real Estate"),
-        }
-    }
+pub use compliance_core::{
+    Action, ActionRuleSets, ActionRules, AddressOverride, Amount, ComplianceInput,
+    ComplianceOutcome, ComplianceRequest, Condition, Date, Eligibility, EvaluationContext,
+    InvestorCategory, InvestorType, KycTier, LevelAttribute, PolicyHash, PoolExposure, PoolId,
+    PoolPolicy, RiskBand, Rule, RuleCheck, TimeOfDay, TraceValue, TradingWindow, Violation,
+    VolumeWindow, Weekday, WindowAllowance, WindowVolume,
+};
+
+/// Encodes a value the way `env::read` in the guest expects it on stdin.
+pub trait ToGuestStdin {
+    fn to_guest_stdin(&self) -> Result<Vec<u8>>;
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
-#[schema(
-    example = json!({
-        "user": "0x2111222233334444555566667777888899990000",
-        "pool_id": "gold",
-        "residency": "US",
-        "kyc_level": 2,
-        "aml_passed": true,
-        "accredited_investor": true,
-        "exposure_musd": 20000,
-        "requested_amount": 10000,
-        "risk_score": 3
-    })
-)]
-pub struct ComplianceRequest {
-    #[schema(example = "0x2111222233334444555566667777888899990000")]
-    pub user: String,
-    #[schema(example = "gold")]
-    pub pool_id: PoolId,
-    #[schema(example = "US")]
-    pub residency: String,
-    #[schema(example = 2, minimum = 0, maximum = 3)]
-    pub kyc_level: u8,
-    #[schema(example = true)]
-    pub aml_passed: bool,
-    #[schema(example = true)]
-    pub accredited_investor: bool,
-    #[schema(example = 20000)]
-    pub exposure_musd: u64,
-    #[schema(example = 10000)]
-    pub requested_amount: u64,
-    #[schema(example = 3, minimum = 0, maximum = 10)]
-    pub risk_score: u8,
-}
-
-impl ComplianceRequest {
-    pub fn to_guest_stdin(&self) -> Result<Vec<u8>> {
+impl<T: Serialize> ToGuestStdin for T {
+    fn to_guest_stdin(&self) -> Result<Vec<u8>> {
         let words = to_vec(self)?;
         let mut bytes = Vec::with_capacity(words.len() * 4);
         for word in words {
@@ -69,37 +28,13 @@ impl ComplianceRequest {
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
-#[schema(
-    example = json!({
-        "user": "0x2111222233334444555566667777888899990000",
-        "pool_id": "gold",
-        "allowed": true,
-        "reason": "All compliance checks passed",
-        "max_allocation": 50000,
-        "requested_amount": 10000,
-        "exposure_musd": 20000
-    })
-)]
-pub struct ComplianceOutcome {
-    #[schema(example = "0x2111222233334444555566667777888899990000")]
-    pub user: String,
-    #[schema(example = "gold")]
-    pub pool_id: PoolId,
-    #[schema(example = true)]
-    pub allowed: bool,
-    #[schema(example = "All compliance checks passed")]
-    pub reason: String,
-    #[schema(example = 50000)]
-    pub max_allocation: u64,
-    #[schema(example = 10000)]
-    pub requested_amount: u64,
-    #[schema(example = 20000)]
-    pub exposure_musd: u64,
-}
-
-impl ComplianceOutcome {
-    pub fn from_journal(bytes: &[u8]) -> Result<Self> {
+/// Decodes a value committed by the guest with `env::commit`.
+pub trait FromJournal: Sized {
+    fn from_journal(bytes: &[u8]) -> Result<Self>;
+}
+
+impl<T: DeserializeOwned> FromJournal for T {
+    fn from_journal(bytes: &[u8]) -> Result<Self> {
         Ok(from_slice(bytes)?)
     }
 }
@@ -112,6 +47,12 @@ pub struct ProofMetadata {
     pub seal: Vec<u8>,
     #[schema(value_type = String, example = "0x123456789abcdef")]
     pub id: U256,
+    /// Hash of the policy committed to the journal; pools pin this to a policy document.
+    #[schema(
+        value_type = String,
+        example = "0x9f2c4e0d6b1a8f3e5c7d9b2a4f6e8c0d1b3a5f7e9c2d4b6a8f0e1c3d5b7a9f2c"
+    )]
+    pub policy_hash: PolicyHash,
     #[schema(example = "Gold proof | allowed: true | reason: All compliance checks passed")]
     pub summary: String,
 }
@@ -122,12 +63,14 @@ impl ProofMetadata {
         let id_bytes: [u8; 32] = id.to_be_bytes::<32>();
         let id_hex = hex::encode(id_bytes);
         let summary = format!(
-            "{} proof | allowed: {} | reason: {} | request: {} mUSD | exposure: {} mUSD | seal={} | id={}",
+            "{} proof | allowed: {} | reason: {} | request: {} mUSD | approved: {} mUSD | exposure: {} mUSD | policy={} | seal={} | id={}",
             outcome.pool_id,
             outcome.allowed,
             outcome.reason,
             outcome.requested_amount,
+            outcome.approved_amount,
             outcome.exposure_musd,
+            &outcome.policy_hash.to_string()[0..18],
             &seal_hex[0..std::cmp::min(16, seal_hex.len())],
             &id_hex[0..12]
         );
@@ -136,6 +79,7 @@ impl ProofMetadata {
             journal,
             seal,
             id,
+            policy_hash: outcome.policy_hash,
             summary,
         }
     }
@@ -147,11 +91,22 @@ impl ProofMetadata {
         "outcome": {
             "user": "0x2111222233334444555566667777888899990000",
             "pool_id": "gold",
+            "action": "buy",
+            "tier": null,
+            "address_override": null,
             "allowed": true,
             "reason": "All compliance checks passed",
-            "max_allocation": 50000,
-            "requested_amount": 10000,
-            "exposure_musd": 20000
+            "violations": [],
+            "max_allocation": "50000000000",
+            "requested_amount": "10000000000",
+            "approved_amount": "10000000000",
+            "exposure_musd": "20000000000",
+            "portfolio_exposure_musd": "20000000000",
+            "window_allowances": [],
+            "unlocks_at": null,
+            "evaluated_at": 1767279600,
+            "policy_version": 1,
+            "policy_hash": "0x9f2c4e0d6b1a8f3e5c7d9b2a4f6e8c0d1b3a5f7e9c2d4b6a8f0e1c3d5b7a9f2c"
         },
         "proof": null,
         "message": "Compliance check passed"
@@ -163,3 +118,289 @@ pub struct UserResponse {
     #[schema(example = "Compliance check passed")]
     pub message: String,
 }
+
+/// A pool policy as published by the API, together with the hash the guest commits for it.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct PolicyDocument {
+    #[schema(
+        value_type = String,
+        example = "0x9f2c4e0d6b1a8f3e5c7d9b2a4f6e8c0d1b3a5f7e9c2d4b6a8f0e1c3d5b7a9f2c"
+    )]
+    pub policy_hash: PolicyHash,
+    #[serde(flatten)]
+    pub policy: PoolPolicy,
+}
+
+impl From<&PoolPolicy> for PolicyDocument {
+    fn from(policy: &PoolPolicy) -> Self {
+        Self {
+            policy_hash: policy.policy_hash(),
+            policy: policy.clone(),
+        }
+    }
+}
+
+/// A named group of ISO 3166-1 alpha-2 codes that residency lists may use in place of codes.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct JurisdictionGroup {
+    #[schema(example = "FATF_BLACKLIST")]
+    pub name: String,
+    #[schema(example = json!(["IR", "KP", "MM"]))]
+    pub members: Vec<String>,
+}
+
+/// A pool registered with the API.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct PoolEntry {
+    /// Registry key used in requests and committed to the journal.
+    #[schema(value_type = String, example = "gold")]
+    pub pool_id: PoolId,
+    /// Address of the `RWAPool` created by `RWAPoolFactory`, if deployed.
+    #[serde(default)]
+    #[schema(example = "0x4abd994dd8e6581d909a6acef82e453d3e141d65")]
+    pub address: Option<String>,
+    #[schema(example = "Gold")]
+    pub name: String,
+    #[schema(example = "Gold")]
+    pub asset_symbol: String,
+    /// `policy_id` of the policy the pool is evaluated under.
+    #[schema(example = "gold")]
+    pub policy: String,
+    /// `policy_id` of a candidate policy evaluated alongside `policy` on live requests, to
+    /// measure how many decisions it would change; it never affects responses.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schema(example = json!(null))]
+    pub shadow_policy: Option<String>,
+}
+
+/// A registered pool together with the hash of the policy it is evaluated under and its
+/// holder count.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct PoolInfo {
+    #[serde(flatten)]
+    pub pool: PoolEntry,
+    #[schema(
+        value_type = String,
+        example = "0x9f2c4e0d6b1a8f3e5c7d9b2a4f6e8c0d1b3a5f7e9c2d4b6a8f0e1c3d5b7a9f2c"
+    )]
+    pub policy_hash: PolicyHash,
+    /// Distinct addresses holding a position in the pool.
+    #[schema(example = 42)]
+    pub investor_count: u32,
+    /// The policy's `max_investors`; `null` when the pool has no investor cap.
+    #[schema(example = 99)]
+    pub max_investors: Option<u32>,
+    /// Slots left for new investors; `null` when the pool has no investor cap.
+    #[schema(example = 57)]
+    pub remaining_investor_slots: Option<u32>,
+}
+
+impl PoolInfo {
+    pub fn new(pool: &PoolEntry, policy: &PoolPolicy, investor_count: u32) -> Self {
+        Self {
+            pool: pool.clone(),
+            policy_hash: policy.policy_hash(),
+            investor_count,
+            max_investors: policy.max_investors,
+            remaining_investor_slots: policy.investor_slots_left(investor_count),
+        }
+    }
+}
+
+/// Full evaluation trace returned by `/compliance/explain`; no proof is generated.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+#[schema(
+    example = json!({
+        "outcome": {
+            "user": "0x2111222233334444555566667777888899990000",
+            "pool_id": "gold",
+            "action": "buy",
+            "tier": null,
+            "address_override": null,
+            "allowed": false,
+            "reason": "Risk score 6 exceeds Gold pool limit 4",
+            "violations": [{"risk_score_too_high": {"limit": 4, "provided": 6}}],
+            "max_allocation": "130000000000",
+            "requested_amount": "10000000000",
+            "approved_amount": "0",
+            "exposure_musd": "20000000000",
+            "portfolio_exposure_musd": "20000000000",
+            "window_allowances": [],
+            "unlocks_at": null,
+            "evaluated_at": 1767279600,
+            "policy_version": 1,
+            "policy_hash": "0x9f2c4e0d6b1a8f3e5c7d9b2a4f6e8c0d1b3a5f7e9c2d4b6a8f0e1c3d5b7a9f2c"
+        },
+        "trace": [
+            {"rule": "aml", "threshold": true, "actual": true, "passed": true},
+            {"rule": "residency_allow_list", "threshold": ["US", "CA"], "actual": "US", "passed": true},
+            {"rule": "risk_ceiling", "threshold": 4, "actual": 6, "passed": false}
+        ]
+    })
+)]
+pub struct ExplainResponse {
+    pub outcome: ComplianceOutcome,
+    /// Every rule that was checked, in evaluation order.
+    pub trace: Vec<RuleCheck>,
+}
+
+/// A change that would move a request closer to passing its pool policy.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum RemediationStep {
+    /// Request at most `max_amount` mUSD base units instead.
+    ReduceAmount {
+        max_amount: Amount,
+    },
+    /// Request at least `min_amount` mUSD base units instead.
+    IncreaseAmount {
+        min_amount: Amount,
+    },
+    /// Request a whole number of lots of `lot_size` mUSD base units.
+    RoundToLot {
+        lot_size: Amount,
+    },
+    /// The pool has no capacity left for this user.
+    ExposureCapReached,
+    /// The user's volume allowance for this rolling window is used up; wait for it to roll.
+    VolumeWindowExhausted {
+        period_days: u32,
+    },
+    PassAmlScreening,
+    /// Provide residency as an ISO 3166-1 alpha-2 country code.
+    ProvideResidency,
+    /// The residency is not accepted by this pool; it cannot be remedied here.
+    ResidencyIneligible {
+        residency: String,
+    },
+    RaiseKycLevel {
+        required: u8,
+    },
+    LowerRiskScore {
+        max: u8,
+    },
+    ObtainAccreditation,
+    /// The pool is not open to this investor type (or needs one reported); it cannot be
+    /// remedied here.
+    InvestorTypeIneligible {
+        allowed: Vec<InvestorType>,
+    },
+    /// Get verified for these investor categories.
+    ObtainInvestorCategories {
+        categories: Vec<InvestorCategory>,
+    },
+    /// The user's exposure across all pools has reached the portfolio cap; reduce it elsewhere.
+    PortfolioCapReached,
+    /// The address is on the pool's denylist; it cannot be remedied here.
+    AddressBlocked,
+    /// The position is locked up; retry at `unlocks_at` (Unix seconds).
+    WaitForUnlock {
+        unlocks_at: u64,
+    },
+    /// The pool is full and only admits existing holders; it cannot be remedied here.
+    InvestorCapReached,
+    /// The pool is closed; retry at `opens_at` (Unix seconds), or later if `null`.
+    WaitForTradingWindow {
+        opens_at: Option<u64>,
+    },
+    /// The pool's eligibility expression rejects the request; it cannot be remedied here.
+    EligibilityRuleFailed {
+        rule: String,
+    },
+}
+
+/// What a user would need to change for a request to pass one pool's policy.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct Remediation {
+    /// Whether every rule about the user passes, i.e. only the amount, the timing (lock-up,
+    /// trading hours) or the pool's investor cap (if anything) is blocking.
+    pub eligible: bool,
+    /// Largest amount that fits under the single-trade limit and the remaining exposure cap;
+    /// `null` when the action is not subject to either.
+    #[schema(example = "50000000000")]
+    pub max_amount: Option<Amount>,
+    /// Empty when the request already passes.
+    pub steps: Vec<RemediationStep>,
+}
+
+/// Eligibility of the same user for another registered pool.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct PoolEligibility {
+    #[schema(value_type = String, example = "money_market")]
+    pub pool_id: PoolId,
+    #[schema(example = "Money Market")]
+    pub name: String,
+    #[schema(example = "Money Market Share")]
+    pub asset_symbol: String,
+    /// Whether the request as submitted would pass in this pool.
+    pub allowed: bool,
+    pub remediation: Remediation,
+}
+
+/// Response of `/compliance/remediate`; no proof is generated.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+#[schema(
+    example = json!({
+        "outcome": {
+            "user": "0x2111222233334444555566667777888899990000",
+            "pool_id": "gold",
+            "action": "buy",
+            "tier": null,
+            "address_override": null,
+            "allowed": false,
+            "reason": "Requested amount 60000 mUSD exceeds Gold single-trade limit of 50000 mUSD",
+            "violations": [{"single_trade_limit_exceeded": {"limit": "50000000000", "requested": "60000000000"}}],
+            "max_allocation": "150000000000",
+            "requested_amount": "60000000000",
+            "approved_amount": "0",
+            "exposure_musd": "0",
+            "portfolio_exposure_musd": "0",
+            "window_allowances": [],
+            "unlocks_at": null,
+            "evaluated_at": 1767279600,
+            "policy_version": 1,
+            "policy_hash": "0x9f2c4e0d6b1a8f3e5c7d9b2a4f6e8c0d1b3a5f7e9c2d4b6a8f0e1c3d5b7a9f2c"
+        },
+        "remediation": {
+            "eligible": true,
+            "max_amount": "50000000000",
+            "steps": [{"reduce_amount": {"max_amount": "50000000000"}}]
+        },
+        "alternatives": [
+            {
+                "pool_id": "money_market",
+                "name": "Money Market",
+                "asset_symbol": "Money Market Share",
+                "allowed": false,
+                "remediation": {
+                    "eligible": false,
+                    "max_amount": "25000000000",
+                    "steps": [
+                        {"raise_kyc_level": {"required": 3}},
+                        "obtain_accreditation",
+                        {"reduce_amount": {"max_amount": "25000000000"}}
+                    ]
+                }
+            },
+            {
+                "pool_id": "real_estate",
+                "name": "Real Estate",
+                "asset_symbol": "Real Estate Share",
+                "allowed": false,
+                "remediation": {
+                    "eligible": false,
+                    "max_amount": "200000000000",
+                    "steps": ["obtain_accreditation"]
+                }
+            }
+        ]
+    })
+)]
+pub struct RemediationResponse {
+    /// Evaluation of the request against the pool it names.
+    pub outcome: ComplianceOutcome,
+    pub remediation: Remediation,
+    /// Every other registered pool, evaluated with the same request and no existing exposure.
+    pub alternatives: Vec<PoolEligibility>,
+}
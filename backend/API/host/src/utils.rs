@@ -1,4 +1,16 @@
+use crate::acquisition_store::AcquisitionStore;
+use crate::investor_store::InvestorStore;
+use crate::overrides::OverrideStore;
+use crate::position_store::PositionStore;
+use crate::registry::PoolRegistry;
+use crate::shadow_store::ShadowStore;
+use crate::types::{EvaluationContext, PoolId, PoolPolicy};
+use crate::volume_store::VolumeStore;
 use alloy::signers::local::PrivateKeySigner;
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
 use url::Url;
 
 #[derive(Clone)]
@@ -6,4 +18,53 @@ pub struct AppState {
     pub signer: PrivateKeySigner,
     pub rpc_url: Url,
     pub guest_program_url: Url,
+    pub registry: PoolRegistry,
+    pub volumes: VolumeStore,
+    pub acquisitions: AcquisitionStore,
+    pub investors: InvestorStore,
+    pub positions: PositionStore,
+    pub overrides: OverrideStore,
+    pub shadow: ShadowStore,
+    /// Bearer token for `/admin` routes; admin routes are disabled when unset.
+    pub admin_token: Option<String>,
+}
+
+impl AppState {
+    /// Host-tracked state for `user` in `pool_id`, as handed to the guest.
+    pub fn context(
+        &self,
+        user: &str,
+        pool_id: &PoolId,
+        policy: &PoolPolicy,
+        now: u64,
+    ) -> EvaluationContext {
+        EvaluationContext {
+            window_volumes: self.volumes.window_volumes(user, pool_id, policy, now),
+            address_override: self.overrides.resolve(user, pool_id),
+            last_acquired_at: self.acquisitions.last_acquired_at(user, pool_id),
+            now,
+            investor_count: self.investors.count(pool_id),
+            existing_investor: self.investors.is_holder(user, pool_id),
+            position: self.positions.position(user, pool_id),
+        }
+    }
+}
+
+/// Seconds since the Unix epoch, the clock every evaluation runs against.
+pub fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs())
+}
+
+/// Replaces `path` with `contents` via a sibling temp file, so a crash never leaves a
+/// truncated file behind.
+pub fn write_atomically(path: &Path, contents: &[u8]) -> Result<()> {
+    if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+        fs::create_dir_all(dir).with_context(|| format!("failed to create {}", dir.display()))?;
+    }
+    let tmp = path.with_extension("json.tmp");
+    fs::write(&tmp, contents).with_context(|| format!("failed to write {}", tmp.display()))?;
+    fs::rename(&tmp, path).with_context(|| format!("failed to replace {}", path.display()))?;
+    Ok(())
 }
@@ -0,0 +1,241 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use crate::types::{Amount, PoolId, PoolPolicy, VolumeWindow, WindowVolume};
+use crate::utils::write_atomically;
+
+pub const DEFAULT_VOLUME_STORE_PATH: &str = "data/volume.json";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct VolumeRecord {
+    user: String,
+    pool_id: PoolId,
+    amount: Amount,
+    /// Unix seconds at which the inflow was approved.
+    timestamp: u64,
+}
+
+/// Approved inflow volume per user and pool, persisted as a JSON file so rolling-window caps
+/// survive restarts.
+#[derive(Debug, Clone)]
+pub struct VolumeStore {
+    path: PathBuf,
+    /// Records older than the longest configured window are dropped on the next write.
+    retention_secs: u64,
+    records: Arc<Mutex<Vec<VolumeRecord>>>,
+}
+
+impl VolumeStore {
+    /// Opens the store at `path`, starting empty if the file does not exist yet.
+    pub fn load(path: impl AsRef<Path>, retention_secs: u64) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let records: Vec<VolumeRecord> = if path.exists() {
+            let raw = fs::read_to_string(&path)
+                .with_context(|| format!("failed to read volume store {}", path.display()))?;
+            serde_json::from_str(&raw)
+                .with_context(|| format!("invalid volume store {}", path.display()))?
+        } else {
+            Vec::new()
+        };
+        println!(
+            "✅ Loaded {} volume records from {}",
+            records.len(),
+            path.display()
+        );
+
+        Ok(Self {
+            path,
+            retention_secs,
+            records: Arc::new(Mutex::new(records)),
+        })
+    }
+
+    /// Volume approved for `user` in `pool_id` within each of `policy`'s windows, ending `now`.
+    pub fn window_volumes(
+        &self,
+        user: &str,
+        pool_id: &PoolId,
+        policy: &PoolPolicy,
+        now: u64,
+    ) -> Vec<WindowVolume> {
+        let records = self.records.lock().unwrap();
+        policy
+            .volume_windows
+            .iter()
+            .map(|window| WindowVolume {
+                period_days: window.period_days,
+                used: used_in(&records, user, pool_id, window, now),
+            })
+            .collect()
+    }
+
+    /// Records `amount` as approved for `user` in `pool_id` unless it would take them past one
+    /// of `windows`, returning whether it fit.
+    ///
+    /// The check and the record happen under one lock, so concurrent requests cannot both be
+    /// approved against the same room. The caller reserves when it decides, before proving,
+    /// and [`VolumeStore::release`]s the reservation if the proof does not go through.
+    pub fn reserve(
+        &self,
+        user: &str,
+        pool_id: &PoolId,
+        amount: Amount,
+        windows: &[VolumeWindow],
+        now: u64,
+    ) -> Result<bool> {
+        let mut records = self.records.lock().unwrap();
+        let fits = windows.iter().all(|window| {
+            used_in(&records, user, pool_id, window, now).saturating_add(amount)
+                <= window.max_volume
+        });
+        if !fits {
+            return Ok(false);
+        }
+        let cutoff = now.saturating_sub(self.retention_secs);
+        records.retain(|record| record.timestamp > cutoff);
+        records.push(VolumeRecord {
+            user: user.to_ascii_lowercase(),
+            pool_id: pool_id.clone(),
+            amount,
+            timestamp: now,
+        });
+        write_atomically(&self.path, &serde_json::to_vec(&*records)?).map(|()| true)
+    }
+
+    /// Drops a reservation made by [`VolumeStore::reserve`] with the same arguments, freeing
+    /// its volume.
+    pub fn release(&self, user: &str, pool_id: &PoolId, amount: Amount, now: u64) -> Result<()> {
+        let mut records = self.records.lock().unwrap();
+        let user = user.to_ascii_lowercase();
+        let Some(index) = records.iter().rposition(|record| {
+            record.user == user
+                && record.pool_id == *pool_id
+                && record.amount == amount
+                && record.timestamp == now
+        }) else {
+            return Ok(());
+        };
+        records.remove(index);
+        write_atomically(&self.path, &serde_json::to_vec(&*records)?)
+    }
+}
+
+/// Volume approved for `user` in `pool_id` within `window`, ending `now`.
+fn used_in(
+    records: &[VolumeRecord],
+    user: &str,
+    pool_id: &PoolId,
+    window: &VolumeWindow,
+    now: u64,
+) -> Amount {
+    let since = now.saturating_sub(window.period_secs());
+    records
+        .iter()
+        .filter(|record| {
+            record.user.eq_ignore_ascii_case(user)
+                && record.pool_id == *pool_id
+                && record.timestamp > since
+        })
+        .fold(Amount::ZERO, |total, record| {
+            total.saturating_add(record.amount)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{policy, temp_path};
+
+    const NOW: u64 = 1_767_279_600;
+
+    fn store(name: &str) -> VolumeStore {
+        VolumeStore::load(temp_path(&format!("volume-{}", name)), 30 * 86_400).unwrap()
+    }
+
+    fn window() -> VolumeWindow {
+        VolumeWindow {
+            period_days: 30,
+            max_volume: Amount::from_musd(100_000),
+        }
+    }
+
+    #[test]
+    fn second_request_inside_the_window_is_denied() {
+        let volumes = store("second-request");
+        let gold = PoolId::new("gold");
+        let amount = Amount::from_musd(60_000);
+
+        assert!(volumes
+            .reserve("0xAbC", &gold, amount, &[window()], NOW)
+            .unwrap());
+        assert!(!volumes
+            .reserve("0xabc", &gold, amount, &[window()], NOW + 1)
+            .unwrap());
+        // Other users and pools have their own room.
+        assert!(volumes
+            .reserve("0xdef", &gold, amount, &[window()], NOW + 1)
+            .unwrap());
+        assert!(volumes
+            .reserve(
+                "0xabc",
+                &PoolId::new("silver"),
+                amount,
+                &[window()],
+                NOW + 1
+            )
+            .unwrap());
+    }
+
+    #[test]
+    fn released_reservation_frees_the_volume() {
+        let volumes = store("released");
+        let gold = PoolId::new("gold");
+        let amount = Amount::from_musd(60_000);
+
+        assert!(volumes
+            .reserve("0xabc", &gold, amount, &[window()], NOW)
+            .unwrap());
+        volumes.release("0xABC", &gold, amount, NOW).unwrap();
+        assert!(volumes
+            .reserve("0xabc", &gold, amount, &[window()], NOW + 1)
+            .unwrap());
+    }
+
+    #[test]
+    fn reservations_outside_the_window_do_not_count() {
+        let volumes = store("outside");
+        let gold = PoolId::new("gold");
+        let amount = Amount::from_musd(60_000);
+
+        assert!(volumes
+            .reserve("0xabc", &gold, amount, &[window()], NOW)
+            .unwrap());
+        let later = NOW + window().period_secs();
+        assert!(volumes
+            .reserve("0xabc", &gold, amount, &[window()], later)
+            .unwrap());
+    }
+
+    #[test]
+    fn reservations_survive_a_reload() {
+        let volumes = store("reload");
+        let gold = PoolId::new("gold");
+        volumes
+            .reserve("0xabc", &gold, Amount::from_musd(60_000), &[], NOW)
+            .unwrap();
+
+        let reloaded = VolumeStore::load(&volumes.path, volumes.retention_secs).unwrap();
+        let mut policy = policy();
+        policy.volume_windows = vec![window()];
+        assert_eq!(
+            reloaded.window_volumes("0xabc", &gold, &policy, NOW + 1),
+            vec![WindowVolume {
+                period_days: 30,
+                used: Amount::from_musd(60_000),
+            }]
+        );
+    }
+}
@@ -0,0 +1,478 @@
+//! Differential tests: the host's preliminary evaluation must match the outcome the guest
+//! commits to the journal for every input.
+//!
+//! Executing the guest needs the RISC Zero toolchain, so `host_matches_guest` runs wherever the
+//! guest is built and passes vacuously when the embedded ELF is empty (`RISC0_SKIP_BUILD`).
+
+use compliance_core::{
+    evaluate, Action, ActionRuleSets, ActionRules, AddressOverride, Amount, AmountAttribute,
+    Comparison, ComplianceInput, ComplianceOutcome, ComplianceRequest, Condition, Date,
+    Eligibility, EvaluationContext, InvestorCategory, InvestorType, KycTier, LevelAttribute,
+    PoolExposure, PoolId, PoolPolicy, RiskBand, Rule, TimeOfDay, TradingWindow, VolumeWindow,
+    Weekday, WindowVolume,
+};
+use methods::GUEST_CODE_FOR_ZK_PROOF_ELF;
+use proptest::prelude::*;
+use risc0_zkvm::{default_executor, serde::from_slice, serde::to_vec, ExecutorEnv};
+use std::ops::Range;
+
+fn guest_available() -> bool {
+    !GUEST_CODE_FOR_ZK_PROOF_ELF.is_empty()
+}
+
+fn execute_guest(input: &ComplianceInput) -> ComplianceOutcome {
+    let env = ExecutorEnv::builder()
+        .write(input)
+        .unwrap()
+        .build()
+        .unwrap();
+    let session = default_executor()
+        .execute(env, GUEST_CODE_FOR_ZK_PROOF_ELF)
+        .unwrap();
+    session.journal.decode().unwrap()
+}
+
+fn pool_id() -> impl Strategy<Value = PoolId> {
+    prop_oneof![
+        prop::sample::select(vec!["gold", "money_market", "real_estate"]).prop_map(PoolId::new),
+        "0x[0-9a-f]{40}".prop_map(PoolId::new),
+    ]
+}
+
+fn residency() -> impl Strategy<Value = String> {
+    prop_oneof![
+        prop::sample::select(vec![
+            "US", "us", "GB", "UK", "SG", "AE", "RU", "kp", "BR", ""
+        ])
+        .prop_map(str::to_string),
+        "[A-Za-z]{0,3}",
+    ]
+}
+
+fn residencies() -> impl Strategy<Value = Vec<String>> {
+    prop::collection::vec(
+        prop::sample::select(vec!["US", "GB", "SG", "AE", "RU", "KP"]).prop_map(str::to_string),
+        0..4,
+    )
+}
+
+/// Whole mUSD in `range` plus an arbitrary fraction, so base units below one mUSD are covered.
+fn musd(range: Range<u64>) -> impl Strategy<Value = Amount> {
+    (range, 0u128..Amount::from_musd(1).base_units())
+        .prop_map(|(whole, fraction)| Amount(Amount::from_musd(whole).base_units() + fraction))
+}
+
+fn amount() -> impl Strategy<Value = Amount> {
+    prop_oneof![musd(0..600_000), Just(Amount::MAX)]
+}
+
+fn cap() -> impl Strategy<Value = Option<Amount>> {
+    prop::option::of(musd(1..600_000))
+}
+
+prop_compose! {
+    fn action_rules()(
+        rules in prop::sample::subsequence(Rule::ALL.to_vec(), 0..=Rule::ALL.len()),
+        max_single_trade in cap(),
+        max_total_exposure in cap(),
+    ) -> ActionRules {
+        ActionRules {
+            rules,
+            max_single_trade,
+            max_total_exposure,
+        }
+    }
+}
+
+prop_compose! {
+    fn action_rule_sets()(
+        buy in action_rules(),
+        sell in action_rules(),
+        add_liquidity in action_rules(),
+        remove_liquidity in action_rules(),
+    ) -> ActionRuleSets {
+        ActionRuleSets {
+            buy,
+            sell,
+            add_liquidity,
+            remove_liquidity,
+        }
+    }
+}
+
+fn investor_types() -> impl Strategy<Value = Vec<InvestorType>> {
+    prop::sample::subsequence(vec![InvestorType::Individual, InvestorType::Entity], 0..=2)
+}
+
+fn investor_categories() -> impl Strategy<Value = Vec<InvestorCategory>> {
+    prop::sample::subsequence(
+        vec![
+            InvestorCategory::Accredited,
+            InvestorCategory::QualifiedPurchaser,
+            InvestorCategory::ProfessionalClient,
+            InvestorCategory::Institutional,
+        ],
+        0..=4,
+    )
+}
+
+fn period_days() -> impl Strategy<Value = u32> {
+    prop::sample::select(vec![1u32, 7, 30, 365])
+}
+
+fn volume_windows() -> impl Strategy<Value = Vec<VolumeWindow>> {
+    prop::collection::vec(
+        (period_days(), musd(1..600_000)).prop_map(|(period_days, max_volume)| VolumeWindow {
+            period_days,
+            max_volume,
+        }),
+        0..3,
+    )
+}
+
+fn context() -> impl Strategy<Value = EvaluationContext> {
+    (
+        prop::collection::vec(
+            (period_days(), amount())
+                .prop_map(|(period_days, used)| WindowVolume { period_days, used }),
+            0..4,
+        ),
+        prop::option::of(prop::sample::select(vec![
+            AddressOverride::Allow,
+            AddressOverride::Deny,
+        ])),
+        prop::option::of(0u64..2_000_000_000),
+        0u64..2_000_000_000,
+        0u32..120,
+        any::<bool>(),
+        prop::option::of(amount()),
+    )
+        .prop_map(
+            |(
+                window_volumes,
+                address_override,
+                last_acquired_at,
+                now,
+                investor_count,
+                existing_investor,
+                position,
+            )| EvaluationContext {
+                window_volumes,
+                address_override,
+                last_acquired_at,
+                now,
+                investor_count,
+                existing_investor,
+                position,
+            },
+        )
+}
+
+fn portfolio() -> impl Strategy<Value = Vec<PoolExposure>> {
+    prop::collection::vec(
+        (pool_id(), amount()).prop_map(|(pool_id, exposure_musd)| PoolExposure {
+            pool_id,
+            exposure_musd,
+        }),
+        0..4,
+    )
+}
+
+fn tiers() -> impl Strategy<Value = Vec<KycTier>> {
+    prop::collection::vec(
+        (
+            0u8..=4,
+            any::<bool>(),
+            musd(1..300_000),
+            musd(1..600_000),
+            prop::option::of(musd(1..1_200_000)),
+        ),
+        0..4,
+    )
+    .prop_map(|tiers| {
+        tiers
+            .into_iter()
+            .enumerate()
+            .map(
+                |(
+                    index,
+                    (
+                        min_kyc_level,
+                        require_accreditation,
+                        max_single_trade,
+                        max_total_exposure,
+                        max_portfolio_exposure,
+                    ),
+                )| {
+                    KycTier {
+                        name: format!("tier_{index}"),
+                        min_kyc_level,
+                        require_accreditation,
+                        max_single_trade,
+                        max_total_exposure,
+                        max_portfolio_exposure,
+                    }
+                },
+            )
+            .collect()
+    })
+}
+
+fn date() -> impl Strategy<Value = Date> {
+    // 1970-01-01 to 2099-12-31.
+    (0u32..47_482).prop_map(Date)
+}
+
+fn trading_windows() -> impl Strategy<Value = Vec<TradingWindow>> {
+    prop::collection::vec(
+        (
+            prop::sample::subsequence(Weekday::ALL.to_vec(), 0..=7),
+            0u32..96,
+            1u32..=96,
+        )
+            .prop_map(|(weekdays, opens, length)| TradingWindow {
+                weekdays,
+                opens: TimeOfDay(opens * 900),
+                closes: TimeOfDay((opens + length).min(96) * 900),
+            }),
+        0..3,
+    )
+}
+
+fn comparison() -> impl Strategy<Value = Comparison> {
+    prop::sample::select(vec![
+        Comparison::Eq,
+        Comparison::Ne,
+        Comparison::Lt,
+        Comparison::Le,
+        Comparison::Gt,
+        Comparison::Ge,
+    ])
+}
+
+/// Conditions as the parser produces them: compound nodes have at least two operands and
+/// lists are sorted and deduplicated.
+fn condition() -> impl Strategy<Value = Condition> {
+    let leaf = prop_oneof![
+        any::<bool>().prop_map(Condition::Const),
+        Just(Condition::AmlPassed),
+        prop::sample::select(InvestorCategory::ALL.to_vec()).prop_map(Condition::Holds),
+        (
+            prop::sample::select(vec![LevelAttribute::KycLevel, LevelAttribute::RiskScore]),
+            comparison(),
+            0u8..=10,
+        )
+            .prop_map(|(attribute, comparison, value)| Condition::Level {
+                attribute,
+                comparison,
+                value,
+            }),
+        (
+            prop::sample::select(vec![
+                AmountAttribute::ExposureMusd,
+                AmountAttribute::RequestedAmount,
+            ]),
+            comparison(),
+            amount(),
+        )
+            .prop_map(|(attribute, comparison, value)| Condition::Amount {
+                attribute,
+                comparison,
+                value,
+            }),
+        residencies()
+            .prop_filter("empty list", |codes| !codes.is_empty())
+            .prop_map(|mut codes| {
+                codes.sort();
+                codes.dedup();
+                Condition::ResidencyIn(codes)
+            }),
+        prop::sample::subsequence(InvestorType::ALL.to_vec(), 1..=2)
+            .prop_map(Condition::InvestorTypeIn),
+    ];
+    leaf.prop_recursive(3, 16, 3, |inner| {
+        prop_oneof![
+            prop::collection::vec(inner.clone(), 2..4).prop_map(Condition::All),
+            prop::collection::vec(inner.clone(), 2..4).prop_map(Condition::Any),
+            inner.prop_map(|condition| Condition::Not(Box::new(condition))),
+        ]
+    })
+}
+
+fn risk_bands() -> impl Strategy<Value = Vec<RiskBand>> {
+    prop::collection::vec(
+        (0u8..=10, 0u16..=10_000).prop_map(|(max_risk_score, allocation_bps)| RiskBand {
+            max_risk_score,
+            allocation_bps,
+        }),
+        0..4,
+    )
+}
+
+prop_compose! {
+    fn policy()(
+        policy_id in "[a-z_]{1,12}",
+        version in 1u32..=5,
+        effective_from in 0u64..2_000_000_000,
+        allowed_residencies in residencies(),
+        banned_residencies in residencies(),
+        max_single_trade in musd(1..300_000),
+        max_total_exposure in musd(1..600_000),
+        min_kyc_level in 0u8..=3,
+        max_risk_score in 0u8..=10,
+        require_accreditation in any::<bool>(),
+        allowed_investor_types in investor_types(),
+        required_investor_categories in investor_categories(),
+        volume_windows in volume_windows(),
+        tiers in tiers(),
+        risk_bands in risk_bands(),
+        max_portfolio_exposure in prop::option::of(musd(1..1_200_000)),
+        lockup_days in prop::option::of(1u32..=730),
+        max_investors in prop::option::of(1u32..=100),
+        min_trade in prop::option::of(musd(1..50_000)),
+        lot_size in prop::option::of(prop::sample::select(vec![
+            Amount::from_musd(1),
+            Amount::from_musd(1_000),
+            Amount::from_musd(10_000),
+        ])),
+        min_initial_investment in prop::option::of(musd(1..100_000)),
+        trading_windows in trading_windows(),
+        holiday_calendar in prop::option::of(Just("us_market".to_string())),
+        holidays in prop::collection::vec(date(), 0..4),
+        eligibility in prop::option::of(condition().prop_map(Eligibility)),
+        actions in action_rule_sets(),
+    ) -> PoolPolicy {
+        PoolPolicy {
+            name: policy_id.to_uppercase(),
+            policy_id,
+            version,
+            effective_from,
+            allowed_residencies,
+            banned_residencies,
+            max_single_trade,
+            max_total_exposure,
+            min_kyc_level,
+            max_risk_score,
+            require_accreditation,
+            allowed_investor_types,
+            required_investor_categories,
+            volume_windows,
+            tiers,
+            risk_bands,
+            max_portfolio_exposure,
+            lockup_days,
+            max_investors,
+            min_trade,
+            lot_size,
+            min_initial_investment,
+            trading_windows,
+            holiday_calendar,
+            holidays,
+            eligibility,
+            actions,
+        }
+    }
+}
+
+prop_compose! {
+    fn request()(
+        pool_id in pool_id(),
+        action in prop::sample::select(Action::ALL.to_vec()),
+        residency in residency(),
+        kyc_level in 0u8..=4,
+        aml_passed in any::<bool>(),
+        accredited_investor in any::<bool>(),
+        investor_type in prop::option::of(prop::sample::select(vec![
+            InvestorType::Individual,
+            InvestorType::Entity,
+        ])),
+        investor_categories in investor_categories(),
+        exposure_musd in amount(),
+        requested_amount in amount(),
+        risk_score in 0u8..=10,
+        allow_partial in any::<bool>(),
+        portfolio in portfolio(),
+    ) -> ComplianceRequest {
+        ComplianceRequest {
+            user: "0x2111222233334444555566667777888899990000".to_string(),
+            pool_id,
+            action,
+            residency,
+            kyc_level,
+            aml_passed,
+            accredited_investor,
+            investor_type,
+            investor_categories,
+            exposure_musd,
+            requested_amount,
+            risk_score,
+            allow_partial,
+            portfolio,
+        }
+    }
+}
+
+fn input() -> impl Strategy<Value = ComplianceInput> {
+    (request(), policy(), context()).prop_map(|(request, policy, context)| ComplianceInput {
+        request,
+        policy,
+        context,
+    })
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(64))]
+
+    #[test]
+    fn guest_stdin_round_trips(input in input()) {
+        let words = to_vec(&input).unwrap();
+        let decoded: ComplianceInput = from_slice(&words).unwrap();
+        prop_assert_eq!(&decoded, &input);
+        prop_assert_eq!(
+            evaluate(&decoded.request, &decoded.policy, &decoded.context),
+            evaluate(&input.request, &input.policy, &input.context)
+        );
+    }
+
+    #[test]
+    fn amount_strings_round_trip(amount in amount()) {
+        prop_assert_eq!(format!("{amount} mUSD").parse::<Amount>(), Ok(amount));
+        prop_assert_eq!(amount.base_units().to_string().parse::<Amount>(), Ok(amount));
+    }
+
+    #[test]
+    fn calendar_strings_round_trip(date in date(), minutes in 0u32..=1_440) {
+        prop_assert_eq!(date.to_string().parse::<Date>(), Ok(date));
+        let time = TimeOfDay(minutes * 60);
+        prop_assert_eq!(time.to_string().parse::<TimeOfDay>(), Ok(time));
+    }
+
+    #[test]
+    fn eligibility_strings_round_trip(condition in condition()) {
+        let eligibility = Eligibility(condition);
+        prop_assert_eq!(eligibility.to_string().parse::<Eligibility>(), Ok(eligibility));
+    }
+
+    #[test]
+    fn host_matches_guest(input in input()) {
+        if !guest_available() {
+            return Ok(());
+        }
+        prop_assert_eq!(execute_guest(&input), evaluate(&input.request, &input.policy, &input.context));
+    }
+}
+
+#[test]
+fn guest_stdin_rejects_eligibility_nested_too_deep() {
+    let nested = |depth: usize| {
+        (0..depth).fold(Condition::AmlPassed, |condition, _| {
+            Condition::Not(Box::new(condition))
+        })
+    };
+
+    let words = to_vec(&nested(Eligibility::MAX_DEPTH)).unwrap();
+    assert!(from_slice::<Eligibility, _>(&words).is_ok());
+
+    let words = to_vec(&nested(Eligibility::MAX_DEPTH + 1)).unwrap();
+    assert!(from_slice::<Eligibility, _>(&words).is_err());
+}
@@ -2,6 +2,7 @@
 
 import { useState, useEffect } from 'react';
 import { useAppKitAccount } from '@reown/appkit/react';
+import { parseMUSD } from '@/utils/decimals';
 
 interface ComplianceFormData {
   residency: string;
@@ -29,7 +30,7 @@ interface ComplianceProof {
   outcome: {
     allowed: boolean;
     reason: string;
-    max_allocation: number;
+    max_allocation: string;
   };
 }
 
@@ -49,7 +50,7 @@ const POOL_REQUIREMENTS: Record<string, PoolRequirements> = {
     name: 'Gold Pool',
     description: 'Trade tokenized gold with mUSD',
     requirements: [
-      'Residency in US, CA, UK, DE, FR, SG, or AE',
+      'Residency in US, CA, GB, DE, FR, SG, or AE',
       'KYC Level 2 or higher',
       'Risk score 4 or lower',
       'Passed AML screening',
@@ -59,13 +60,13 @@ const POOL_REQUIREMENTS: Record<string, PoolRequirements> = {
     min_kyc_level: 2,
     max_risk_score: 4,
     require_accreditation: false,
-    allowed_residencies: ['US', 'CA', 'UK', 'DE', 'FR', 'SG', 'AE']
+    allowed_residencies: ['US', 'CA', 'GB', 'DE', 'FR', 'SG', 'AE']
   },
   money_market: {
     name: 'Money Market Pool',
     description: 'Access money market instruments',
     requirements: [
-      'Residency in US, CA, UK, DE, or FR',
+      'Residency in US, CA, GB, DE, or FR',
       'KYC Level 3 or higher',
       'Risk score 3 or lower',
       'Accredited investor status required',
@@ -76,7 +77,7 @@ const POOL_REQUIREMENTS: Record<string, PoolRequirements> = {
     min_kyc_level: 3,
     max_risk_score: 3,
     require_accreditation: true,
-    allowed_residencies: ['US', 'CA', 'UK', 'DE', 'FR']
+    allowed_residencies: ['US', 'CA', 'GB', 'DE', 'FR']
   },
   real_estate: {
     name: 'Real Estate Pool',
@@ -169,8 +170,9 @@ export function ComplianceModal({
           kyc_level: formData.kyc_level,
           aml_passed: formData.aml_passed,
           accredited_investor: formData.accredited_investor,
-          exposure_musd: formData.exposure_musd,
-          requested_amount: formData.requested_amount,
+          // The API takes amounts as strings of mUSD base units, like RWAPool.swap.
+          exposure_musd: parseMUSD(String(formData.exposure_musd)).toString(),
+          requested_amount: parseMUSD(String(formData.requested_amount)).toString(),
           risk_score: formData.risk_score
         })
       });
@@ -259,7 +261,7 @@ export function ComplianceModal({
             <div className="form-group">
               <label>
                 Country of Residency (2-letter code) *
-                <span className="help-text">e.g., US, UK, CA, DE</span>
+                <span className="help-text">e.g., US, GB, CA, DE</span>
               </label>
               <input
                 type="text"
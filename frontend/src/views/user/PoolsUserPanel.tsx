@@ -31,7 +31,7 @@ interface ComplianceProof {
   outcome: {
     allowed: boolean;
     reason: string;
-    max_allocation: number;
+    max_allocation: string;
   };
 }
 